@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AdvisoryAction } from "./AdvisoryAction";
 import type { AdvisoryCondition } from "./AdvisoryCondition";
 import type { AdvisoryLevel } from "./AdvisoryLevel";
 
@@ -11,16 +12,19 @@ name: string, level: AdvisoryLevel,
 /**
  * The message template for this advisory, used to generate notices and when applying
  * the advisory to a user.
- * String substitution may be applied by the matching condition(s).
- * Known variables:
- * - `{{:username:}}`: the user's display name.
- * - `{{:account_age_days:}}`: the user's account age in days (with [AdvisoryCondition::AccountAgeAtMostDays]).
- * - `{{:group_name:}}`: the name of the relevant group (with [AdvisoryCondition::IsGroupMember]). Some groups
+ * See [crate::advisories::templating::KNOWN_TEMPLATE_VARIABLES] for the available variables.
+ * Some are filled for every user (i.e. `{{:username:}}`, `{{:trust_rank:}}`, `{{:instance_name:}}`),
+ * and some only by the matching condition(s) (i.e. `{{:group_name:}}`). Some groups
  * frequently change names to avoid identification. You may want to keep the original group name in the advisory.
  *
  * Known patterns:
- * - `{{:variable_name:}}`: the value of `variable_name`. TODO: what if it isn't defined?
+ * - `{{:variable_name:}}`: the value of `variable_name`, or nothing if it isn't defined.
  * - `{{:variable_name||default_value:}}`: the value of `variable_name`, or `default_value` if it isn't defined.
+ * - `{{:variable_name|upper:}}`, `{{:variable_name|truncate:20:}}`, `{{:join_time|relative_time:}}`: filters, applied in order.
+ * - `{{:#if variable_name:}}...{{:else:}}...{{:/if:}}`: only include text if `variable_name` is set and not empty.
+ * Use `{{:#if !variable_name:}}` to negate.
+ *
+ * Templates are validated when the advisory is saved; unknown variables are rejected.
  */
 message_template: string, 
 /**
@@ -47,4 +51,24 @@ send_notification: boolean,
 /**
  * Whether to speak the advisory message via TTS when this advisory matches.
  */
-send_tts: boolean, };
+send_tts: boolean, 
+/**
+ * If set, repeat notices for the same user (i.e. when they rejoin) within this many minutes
+ * don't alert again. They're collapsed into the first notice in History instead.
+ */
+cooldown_minutes: number | null, 
+/**
+ * When this advisory stops applying, in RFC 3339 format. Once it passes, the advisory is
+ * deactivated automatically. Useful for short-term watches ("keep an eye on them for a week").
+ */
+expires_at: string | null, 
+/**
+ * Actions to run when this advisory newly matches a user, in addition to the notice.
+ * Each action runs independently; one failing doesn't stop the others.
+ */
+actions: Array<AdvisoryAction>, 
+/**
+ * How much this advisory adds to a user's risk score when it matches.
+ * If unset, the weight is derived from the level; see [Advisory::effective_weight].
+ */
+weight: number | null, };
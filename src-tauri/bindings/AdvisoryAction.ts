@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * An action run when an advisory newly matches a user.
+ * Templates in actions use the same variables as [Advisory::message_template].
+ */
+export type AdvisoryAction = { "type": "AddToWatchlist", "data": string } | { "type": "CopyUserId" } | { "type": "Webhook", "data": { url: string, payload_template: string, } } | { "type": "IncidentLog", "data": { path: string, line_template: string, } } | { "type": "Osc", "data": { target: string, address: string, argument_template: string, } };
@@ -2,4 +2,4 @@
 import type { AdvisoryGroupCondition } from "./AdvisoryGroupCondition";
 import type { TrustRank } from "./TrustRank";
 
-export type AdvisoryCondition = { "type": "Is", "data": string } | { "type": "IsGroupMember", "data": string } | { "type": "InGroupNameContains", "data": string } | { "type": "GroupCondition", "data": AdvisoryGroupCondition } | { "type": "TrustRankAtMost", "data": TrustRank } | { "type": "AccountAgeAtMostDays", "data": number } | { "type": "UsernameContains", "data": string } | { "type": "PronounContains", "data": string } | { "type": "StatusContains", "data": string } | { "type": "AgeNotVerified" } | { "type": "PlatformIs", "data": string } | { "type": "AvatarMayBe", "data": string } | { "type": "AvatarNameContains", "data": string } | { "type": "AvatarCreatorIs", "data": string } | { "type": "LogLinePrefix", "data": string } | { "type": "InstanceOwner", "data": string } | { "type": "InstanceGroupRestricted", "data": Array<string> | null } | { "type": "Not", "data": { data: AdvisoryCondition, } } | { "type": "AnyOf", "data": Array<AdvisoryCondition> } | { "type": "AllOf", "data": Array<AdvisoryCondition> } | { "type": "None" };
+export type AdvisoryCondition = { "type": "Is", "data": string } | { "type": "IsUntil", "data": { id: string, expires_at: string, } } | { "type": "IsGroupMember", "data": string } | { "type": "InGroupNameContains", "data": string } | { "type": "GroupCondition", "data": AdvisoryGroupCondition } | { "type": "GroupCountAtLeast", "data": { condition: AdvisoryGroupCondition, count: number, } } | { "type": "TrustRankAtMost", "data": TrustRank } | { "type": "AccountAgeAtMostDays", "data": number } | { "type": "HasTag", "data": string } | { "type": "TagPrefix", "data": string } | { "type": "UsernameContains", "data": string } | { "type": "PronounContains", "data": string } | { "type": "StatusContains", "data": string } | { "type": "BioContains", "data": string } | { "type": "BioMatches", "data": string } | { "type": "BioLinkDomainIs", "data": string } | { "type": "AgeNotVerified" } | { "type": "RecentlyKickedWithinHours", "data": number } | { "type": "RecentlyBannedWithinHours", "data": number } | { "type": "TimeInInstanceAtLeastMinutes", "data": number } | { "type": "IsFriend" } | { "type": "IsStaffTrusted" } | { "type": "InWatchlist", "data": string } | { "type": "HasGroupRole", "data": { group_id: string, role_id: string, } } | { "type": "PlatformIs", "data": string } | { "type": "AvatarMayBe", "data": { avatar_id: string, min_confidence: number, } } | { "type": "AvatarNameContains", "data": string } | { "type": "AvatarCreatorIs", "data": string } | { "type": "AvatarIsPrivate" } | { "type": "AvatarIsPublic" } | { "type": "AvatarImageFileIs", "data": string } | { "type": "LogLinePrefix", "data": string } | { "type": "InstanceOwner", "data": string } | { "type": "InstanceGroupRestricted", "data": Array<string> | null } | { "type": "Not", "data": { data: AdvisoryCondition, } } | { "type": "AnyOf", "data": Array<AdvisoryCondition> } | { "type": "AllOf", "data": Array<AdvisoryCondition> } | { "type": "None" };
@@ -3,4 +3,4 @@
 /**
  * Conditions applied to a VRChat Group for an advisory. This way, a set of conditions can be applied to the same group, rather than to any group the user is in.
  */
-export type AdvisoryGroupCondition = { "type": "Id", "data": string } | { "type": "NameContains", "data": string } | { "type": "OwnerIs", "data": string } | { "type": "Not", "data": { data: AdvisoryGroupCondition, } } | { "type": "AnyOf", "data": Array<AdvisoryGroupCondition> } | { "type": "AllOf", "data": Array<AdvisoryGroupCondition> } | { "type": "None" };
+export type AdvisoryGroupCondition = { "type": "Id", "data": string } | { "type": "NameContains", "data": string } | { "type": "OwnerIs", "data": string } | { "type": "MemberCountAtMost", "data": number } | { "type": "CreatedWithinDays", "data": number } | { "type": "IsOpen" } | { "type": "IsVerified" } | { "type": "Not", "data": { data: AdvisoryGroupCondition, } } | { "type": "AnyOf", "data": Array<AdvisoryGroupCondition> } | { "type": "AllOf", "data": Array<AdvisoryGroupCondition> } | { "type": "None" };
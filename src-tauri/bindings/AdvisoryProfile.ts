@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ProfileRule } from "./ProfileRule";
+
+/**
+ * A named set of advisories that applies only in matching instances, i.e. one per community you moderate for.
+ * While a profile is active, only its advisories are evaluated. When no profile matches the instance,
+ * every active advisory applies.
+ */
+export type AdvisoryProfile = { id: string, name: string, 
+/**
+ * Advisories in the profile, by ID.
+ */
+advisory_ids: Array<string>, 
+/**
+ * Advisories with any of these tags are also in the profile.
+ */
+tags: Array<string>, 
+/**
+ * The profile applies to instances matching any of these rules. Profiles are checked in order,
+ * and the first one with a matching rule is used.
+ */
+rules: Array<ProfileRule>, active: boolean, };
@@ -0,0 +1,40 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How often an advisory has matched, kept across restarts so that stale or noisy advisories can be spotted.
+ */
+export type AdvisoryStats = { 
+/**
+ * How many times the advisory was newly applied to a user.
+ */
+totalMatches: number, 
+/**
+ * Matches when a user joined (or their user info was first fetched).
+ */
+joinLeaveMatches: number, 
+/**
+ * Matches when a user switched avatars.
+ */
+avatarSwitchedMatches: number, 
+/**
+ * Matches during a periodic re-evaluation, or after the advisories changed.
+ */
+reevaluationMatches: number, 
+/**
+ * The most recently matched distinct user IDs, oldest first.
+ * Capped at [crate::advisories::stats::MAX_MATCHED_USER_IDS].
+ */
+matchedUserIds: Array<string>, 
+/**
+ * How many distinct users this advisory has matched. Users who dropped off
+ * [Self::matched_user_ids] are counted again if they match again.
+ */
+matchedUserCount: number, 
+/**
+ * Timestamp of the first match, in RFC 3339 format.
+ */
+firstFiredAt: string | null, 
+/**
+ * Timestamp of the most recent match, in RFC 3339 format.
+ */
+lastFiredAt: string | null, };
@@ -0,0 +1,26 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How a condition was evaluated for a user, so moderators can see why an advisory fired.
+ */
+export type ConditionTrace = { 
+/**
+ * The condition's type, i.e. `UsernameContains`.
+ */
+condition: string, 
+/**
+ * The condition's parameter, i.e. the substring to look for.
+ */
+parameter: string | null, matched: boolean, 
+/**
+ * The user's value that was compared, if there is one.
+ */
+value: string | null, 
+/**
+ * Whether the result came from keeping an already-active advisory, because the data needed wasn't available.
+ */
+keptExisting: boolean, 
+/**
+ * Whether the condition wasn't evaluated, because an earlier sibling already decided the result.
+ */
+skipped: boolean, children: Array<ConditionTrace>, };
@@ -0,0 +1,17 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AdvisoryLevel } from "./AdvisoryLevel";
+
+/**
+ * Raises a user's effective level once their risk score (the sum of their matching advisories' weights)
+ * reaches `min_score`, i.e. three Medium advisories (score 6) count as High.
+ */
+export type EscalationRule = { id: string, name: string, min_score: number, 
+/**
+ * The effective level for users who reach `min_score`.
+ */
+level: AdvisoryLevel, 
+/**
+ * The message of the notice sent when a user is escalated by this rule.
+ * Supports the same syntax as [Advisory::message_template], with `{{:risk_score:}}` and `{{:advisory_count:}}`.
+ */
+message_template: string, active: boolean, send_notification: boolean, send_tts: boolean, };
@@ -1,7 +1,17 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { AdvisoryLevel } from "./AdvisoryLevel";
+import type { NoticeState } from "./NoticeState";
 
 export type Notice = { 
+/**
+ * A monotonic ID, assigned when the notice is published. Later notices always have higher IDs.
+ */
+id: number, 
+/**
+ * Bumped whenever the notice changes (i.e. when it repeats), from the same sequence as [Self::id].
+ * Clients that already have the notice can fetch only changes with [crate::notices::get_notices_since].
+ */
+revision: number, 
 /**
  * The title of the notice. Usually "username joined" or "username changed avatars".
  */
@@ -14,4 +24,17 @@ local: boolean, sendTts: boolean, sendNotification: boolean,
 /**
  * Timestamp of when the notice was created, in RFC 3339 format.
  */
-createdAt: string | null, };
+createdAt: string | null, 
+/**
+ * How many more times this notice was raised for the same user during the advisory's
+ * cooldown (i.e. by rejoining). Repeats are collapsed into this notice instead of alerting again.
+ */
+repeatCount: number, 
+/**
+ * Timestamp of the most recent repeat, in RFC 3339 format.
+ */
+lastRepeatedAt: string | null, 
+/**
+ * Where moderators are with this notice. Changed with the commands in [crate::notices].
+ */
+state: NoticeState, };
@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Notice } from "./Notice";
+
+/**
+ * A page of the current instance's notices, newest first.
+ */
+export type NoticeCursorPage = { notices: Array<Notice>, 
+/**
+ * Pass as `before` to get the next (older) page. `None` if there are no older notices.
+ */
+nextCursor: number | null, 
+/**
+ * The latest revision when the page was taken. Pass as `since` to get only what changed after it.
+ */
+cursor: number, 
+/**
+ * Identifies this run of the app. Pass as `session` along with `cursor`.
+ */
+session: string, };
@@ -0,0 +1,21 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Notice } from "./Notice";
+
+/**
+ * Notices that were published or changed after a cursor, oldest change first.
+ */
+export type NoticeDelta = { notices: Array<Notice>, 
+/**
+ * The latest revision. Pass as `since` next time.
+ */
+cursor: number, 
+/**
+ * Identifies this run of the app. Pass as `session` next time.
+ */
+session: string, 
+/**
+ * Whether the notice list was cleared (i.e. on an instance change or restart) after the cursor,
+ * or the cursor is from another session.
+ * If set, drop any notices you have before applying this delta.
+ */
+reset: boolean, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { StoredNotice } from "./StoredNotice";
+
+/**
+ * A page of History, newest first.
+ */
+export type NoticePage = { notices: Array<StoredNotice>, 
+/**
+ * How many notices match the query in total, across all pages.
+ */
+total: number, };
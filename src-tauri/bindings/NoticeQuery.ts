@@ -0,0 +1,27 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AdvisoryLevel } from "./AdvisoryLevel";
+
+/**
+ * Filters for querying History. Unset filters match every notice.
+ */
+export type NoticeQuery = { 
+/**
+ * Only notices at or above this level.
+ */
+minLevel: AdvisoryLevel | null, userId: string | null, advisoryId: string | null, instanceId: string | null, 
+/**
+ * Only notices raised at or after this Unix timestamp.
+ */
+from: number | null, 
+/**
+ * Only notices raised before this Unix timestamp.
+ */
+to: number | null, 
+/**
+ * How many matching notices to skip, newest first.
+ */
+offset: number, 
+/**
+ * How many notices to return at most. 0 uses the default page size.
+ */
+limit: number, };
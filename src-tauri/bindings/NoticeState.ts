@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * The moderation state of a notice. Timestamps are in RFC 3339 format.
+ */
+export type NoticeState = { "type": "New" } | { "type": "Acknowledged", "data": { by: string | null, at: string, } } | { "type": "Claimed", "data": { by: string, at: string, } } | { "type": "Resolved", "data": { by: string | null, note: string, at: string, } };
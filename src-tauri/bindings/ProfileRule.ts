@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Matches instances by their ID. Unset fields match any instance; set fields must all match.
+ */
+export type ProfileRule = { 
+/**
+ * The instance owner (`grp_***` or `usr_***`).
+ */
+owner: string | null, 
+/**
+ * The world ID (`wrld_***`).
+ */
+world: string | null, 
+/**
+ * The instance type, as in [crate::types::VrcMrdInstanceId::instance_type], i.e. "group public".
+ */
+instance_type: string | null, 
+/**
+ * The region code, i.e. "us" or "eu".
+ */
+region: string | null, };
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Notice } from "./Notice";
+
+/**
+ * A notice in History, tagged with where and when it was raised.
+ */
+export type StoredNotice = { 
+/**
+ * The instance the notice was raised in, if known.
+ */
+instanceId: string | null, 
+/**
+ * When the notice was raised, as a Unix timestamp.
+ */
+recordedAt: number, notice: Notice, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A variable that can be used in advisory templates, for the template editor.
+ */
+export type TemplateVariable = { name: string, description: string, 
+/**
+ * Whether the variable is filled for every user (when the information is known),
+ * rather than only by specific conditions.
+ */
+alwaysAvailable: boolean, };
@@ -0,0 +1,170 @@
+use std::{
+    collections::HashMap,
+    path::{Component, Path, PathBuf},
+    time::Duration,
+};
+
+use parking_lot::Mutex;
+use tauri::{Emitter, Manager, Wry};
+use tokio::{io::AsyncWriteExt, net::UdpSocket};
+
+use crate::{
+    advisories::{apply_templating, templating::apply_json_templating, watchlists::add_to_watchlist},
+    memory::advisories::AdvisoryMemory,
+    types::advisories::{Advisory, AdvisoryAction},
+};
+
+/// Run the advisory's actions for a user it newly matched.
+/// Each action runs on its own task and is logged, so a failing action doesn't block the others.
+pub fn run_advisory_actions(
+    app: tauri::AppHandle<Wry>,
+    advisory: &Advisory,
+    user_id: &str,
+    variables: HashMap<&'static str, String>,
+) {
+    for action in advisory.actions.iter().cloned() {
+        let app = app.clone();
+        let advisory_id = advisory.id.clone();
+        let user_id = user_id.to_string();
+        let variables = variables.clone();
+        tauri::async_runtime::spawn(async move {
+            println!(
+                "Running action {:?} for advisory {} on user {}",
+                action, advisory_id, user_id
+            );
+            match run_action(app, &action, &user_id, &variables).await {
+                Ok(()) => println!(
+                    "Action {:?} for advisory {} on user {} succeeded",
+                    action, advisory_id, user_id
+                ),
+                Err(e) => eprintln!(
+                    "Action {:?} for advisory {} on user {} failed: {}",
+                    action, advisory_id, user_id, e
+                ),
+            }
+        });
+    }
+}
+
+async fn run_action(
+    app: tauri::AppHandle<Wry>,
+    action: &AdvisoryAction,
+    user_id: &str,
+    variables: &HashMap<&'static str, String>,
+) -> Result<(), String> {
+    match action {
+        AdvisoryAction::AddToWatchlist(name) => {
+            let added = {
+                let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+                let mut advisory_memory = advisory_memory.lock();
+                add_to_watchlist(&mut advisory_memory.watchlists, name, user_id)
+            };
+            // Already on the list, so there's nothing to save
+            if !added {
+                return Ok(());
+            }
+            crate::advisories::watchlists::save_watchlists(app).await
+        }
+        AdvisoryAction::CopyUserId => {
+            // The clipboard is only reachable from the webview, so the main window does the copying.
+            app.emit("vrcmrd:copy_to_clipboard", user_id.to_string())
+                .map_err(|e| e.to_string())
+        }
+        AdvisoryAction::Webhook { url, payload_template } => {
+            validate_webhook_url(url)?;
+            let payload = apply_json_templating(payload_template, variables);
+            serde_json::from_str::<serde_json::Value>(&payload)
+                .map_err(|e| format!("Payload isn't valid JSON, not sending it: {}", e))?;
+            let response = reqwest::Client::new()
+                .post(url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .timeout(Duration::from_secs(10))
+                .body(payload)
+                .send()
+                .await
+                .map_err(|e| e.to_string())?;
+            if response.status().is_success() {
+                Ok(())
+            } else {
+                Err(format!("Webhook returned HTTP {}", response.status()))
+            }
+        }
+        AdvisoryAction::IncidentLog { path, line_template } => {
+            let path = resolve_incident_log_path(&app, path)?;
+            let line = apply_templating(line_template, variables);
+            if let Some(parent) = path.parent() {
+                tokio::fs::create_dir_all(parent).await.map_err(|e| e.to_string())?;
+            }
+            let mut file = tokio::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .await
+                .map_err(|e| e.to_string())?;
+            file.write_all(format!("{} {}\n", chrono::Local::now().to_rfc3339(), line).as_bytes())
+                .await
+                .map_err(|e| e.to_string())
+        }
+        AdvisoryAction::Osc { target, address, argument_template } => {
+            let argument = apply_templating(argument_template, variables);
+            let socket = UdpSocket::bind("0.0.0.0:0").await.map_err(|e| e.to_string())?;
+            socket
+                .send_to(&encode_osc_message(address, &argument), target.as_str())
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok(())
+        }
+    }
+}
+
+/// Incident logs are kept in this directory, under the app's local data directory.
+const INCIDENT_LOG_DIR: &str = "incident_logs";
+
+/// Check that an incident log path stays inside [INCIDENT_LOG_DIR]: it must be relative, without `..`.
+/// Advisories can be imported and shared, so they mustn't be able to append to arbitrary files.
+pub fn validate_incident_log_path(path: &str) -> Result<(), String> {
+    let path = Path::new(path);
+    if path.as_os_str().is_empty() || !path.components().all(|c| matches!(c, Component::Normal(_))) {
+        return Err(format!(
+            "Incident log path \"{}\" must be a file name (or relative path) inside the incident log folder",
+            path.display()
+        ));
+    }
+    Ok(())
+}
+
+/// Check that a webhook URL is a valid `http` or `https` URL.
+pub fn validate_webhook_url(url: &str) -> Result<(), String> {
+    let parsed = reqwest::Url::parse(url).map_err(|e| format!("Invalid webhook URL \"{}\": {}", url, e))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(format!("Webhook URL \"{}\" must start with http:// or https://", url));
+    }
+    Ok(())
+}
+
+fn resolve_incident_log_path(app: &tauri::AppHandle<Wry>, path: &str) -> Result<PathBuf, String> {
+    validate_incident_log_path(path)?;
+    let data_dir = app
+        .path()
+        .app_local_data_dir()
+        .map_err(|e| e.to_string())?;
+    Ok(data_dir.join(INCIDENT_LOG_DIR).join(path))
+}
+
+/// Encode an OSC message with a single string argument.
+fn encode_osc_message(address: &str, argument: &str) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    push_osc_string(&mut bytes, address);
+    push_osc_string(&mut bytes, ",s");
+    push_osc_string(&mut bytes, argument);
+    bytes
+}
+
+/// OSC strings are null-terminated and padded to a multiple of 4 bytes.
+fn push_osc_string(bytes: &mut Vec<u8>, s: &str) {
+    bytes.extend_from_slice(s.as_bytes());
+    bytes.push(0);
+    while bytes.len() % 4 != 0 {
+        bytes.push(0);
+    }
+}
@@ -0,0 +1,252 @@
+use std::collections::HashMap;
+
+use parking_lot::Mutex;
+use tauri::{Emitter, Manager, Runtime, Wry};
+
+use crate::{
+    advisories::{apply_templating, templating::validate_template},
+    memory::{advisories::AdvisoryMemory, instance::current_instance_id},
+    migrations::VersionedPayload,
+    notices::publish_notice,
+    types::{
+        advisories::{ActiveAdvisory, Advisory, AdvisoryLevel, EscalationRule, Notice},
+        VrcMrdUser,
+    },
+};
+
+/// Escalation rules, raising a user's effective level when their risk score passes a threshold.
+/// This payload was introduced after schema versioning, so it starts at version 0.
+pub const ESCALATION_RULES_PAYLOAD: VersionedPayload = VersionedPayload {
+    key: "escalation_rules",
+    current_version: 0,
+    migrations: &[],
+};
+
+/// How long an escalation notice isn't repeated for the same rule and user, i.e. when they rejoin.
+pub const ESCALATION_COOLDOWN_MINUTES: i64 = 60;
+
+#[tauri::command]
+pub async fn get_escalation_rules(app: tauri::AppHandle<Wry>) -> Result<Vec<EscalationRule>, String> {
+    let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+    let advisory_memory = advisory_memory.lock();
+    Ok(advisory_memory.escalation_rules.clone())
+}
+
+/// Replace all escalation rules. Users are re-scored the next time their advisories are evaluated.
+#[tauri::command]
+pub async fn set_escalation_rules(
+    app: tauri::AppHandle<Wry>,
+    rules: Vec<EscalationRule>,
+) -> Result<(), String> {
+    for rule in rules.iter() {
+        if rule.min_score < 1 {
+            return Err(format!("Escalation rule \"{}\" needs a minimum score of at least 1", rule.name));
+        }
+        validate_template(&rule.message_template)
+            .map_err(|e| format!("Escalation rule \"{}\": {}", rule.name, e))?;
+    }
+    {
+        let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+        let mut advisory_memory = advisory_memory.lock();
+        if !advisory_memory.escalation_rules_loaded {
+            return Err("Escalation rules are still loading".to_string());
+        }
+        advisory_memory.escalation_rules = rules.clone();
+    }
+    let value = serde_json::to_value(&rules).map_err(|e| e.to_string())?;
+    ESCALATION_RULES_PAYLOAD.save(app.clone(), &value).await?;
+    app.emit("vrcmrd:escalation_rules_updated", {})
+        .map_err(|e| e.to_string())
+}
+
+pub async fn load_escalation_rules<R: Runtime>(
+    app: tauri::AppHandle<R>,
+) -> Result<Vec<EscalationRule>, String> {
+    ESCALATION_RULES_PAYLOAD.load_records(app).await
+}
+
+/// Compute the user's risk score from their current advisories, and apply the highest escalation rule
+/// it passes. If the user is newly escalated to a higher level, the rule's notice is published,
+/// unless it was already published for them within [ESCALATION_COOLDOWN_MINUTES].
+///
+/// The risk score is the sum of [crate::types::advisories::Advisory::effective_weight] for each matching advisory.
+/// Call this after updating [VrcMrdUser::advisories].
+pub fn apply_escalation(app: tauri::AppHandle<Wry>, user: &mut VrcMrdUser) {
+    let (risk_score, rule) = {
+        let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+        let advisory_memory = advisory_memory.lock();
+        let risk_score = risk_score(&user.advisories, &advisory_memory.all_advisories);
+        (risk_score, escalation_rule(&advisory_memory.escalation_rules, risk_score).cloned())
+    };
+    let previous_level = user.effective_level.clone();
+    user.risk_score = risk_score;
+    user.effective_level = effective_level(&user.advisories, rule.as_ref());
+    let newly_escalated = match &rule {
+        Some(rule) => {
+            user.escalated_by.as_ref() != Some(&rule.id)
+                && previous_level.map_or(true, |level| (level as u8) < rule.level.clone() as u8)
+        }
+        None => false,
+    };
+    user.escalated_by = rule.as_ref().map(|rule| rule.id.clone());
+    if !newly_escalated {
+        return;
+    }
+    let rule = rule.unwrap();
+    {
+        // The user's escalation is forgotten when they rejoin, so keep the notice from repeating every time
+        let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+        let mut advisory_memory = advisory_memory.lock();
+        let now = chrono::Utc::now().timestamp();
+        let key = (rule.id.clone(), user.id.clone());
+        if advisory_memory
+            .last_notified
+            .get(&key)
+            .is_some_and(|last| now - last < ESCALATION_COOLDOWN_MINUTES * 60)
+        {
+            return;
+        }
+        advisory_memory.last_notified.insert(key, now);
+    }
+    println!(
+        "User {} escalated to {:?} by rule {} (risk score {})",
+        user.id, rule.level, rule.id, risk_score
+    );
+    let mut variables = HashMap::new();
+    variables.insert("username", user.username.clone());
+    variables.insert("user_id", user.id.clone());
+    variables.insert("risk_score", risk_score.to_string());
+    variables.insert("advisory_count", user.advisories.len().to_string());
+    if let Err(e) = publish_notice(
+        app.clone(),
+        Notice {
+            title: Some(format!("“{}” escalated", user.username)),
+            message: apply_templating(&rule.message_template, &variables),
+            level: rule.level.clone(),
+            relevant_user_id: Some(user.id.clone()),
+            local: false,
+            send_tts: rule.send_tts,
+            send_notification: rule.send_notification,
+            created_at: Some(chrono::Utc::now().to_rfc3339()),
+            ..Default::default()
+        },
+        current_instance_id(&app),
+    ) {
+        eprintln!("Failed to publish escalation notice for rule {}: {}", rule.id, e);
+    }
+}
+
+/// The sum of [Advisory::effective_weight] for each of the user's advisories. Deleted advisories don't count.
+fn risk_score(user_advisories: &[ActiveAdvisory], all_advisories: &[Advisory]) -> u32 {
+    user_advisories
+        .iter()
+        .filter_map(|active| all_advisories.iter().find(|a| a.id == active.id))
+        .map(|advisory| advisory.effective_weight())
+        .sum()
+}
+
+/// The highest-level active rule the risk score reaches. Between rules of the same level, the one with the higher `min_score`.
+fn escalation_rule(rules: &[EscalationRule], risk_score: u32) -> Option<&EscalationRule> {
+    rules
+        .iter()
+        .filter(|rule| rule.active && risk_score >= rule.min_score)
+        .max_by_key(|rule| (rule.level.clone() as u8, rule.min_score))
+}
+
+/// The higher of the user's highest advisory level and the escalation rule's level.
+fn effective_level(user_advisories: &[ActiveAdvisory], rule: Option<&EscalationRule>) -> Option<AdvisoryLevel> {
+    let highest_advisory_level = user_advisories
+        .iter()
+        .map(|a| a.level.clone())
+        .max_by_key(|level| level.clone() as u8);
+    match (rule, highest_advisory_level) {
+        (Some(rule), Some(level)) if level.clone() as u8 >= rule.level.clone() as u8 => Some(level),
+        (Some(rule), _) => Some(rule.level.clone()),
+        (None, level) => level,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn advisory(id: &str, level: AdvisoryLevel, weight: Option<u32>) -> Advisory {
+        let mut advisory: Advisory = serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": id,
+            "level": level,
+            "message_template": "",
+            "condition": { "type": "Is", "data": "usr_a" },
+            "tags": [],
+            "active": true,
+            "created_at": "",
+            "updated_at": "",
+            "send_notification": false,
+            "send_tts": false,
+        }))
+        .unwrap();
+        advisory.weight = weight;
+        advisory
+    }
+
+    fn active(advisory: &Advisory) -> ActiveAdvisory {
+        ActiveAdvisory {
+            id: advisory.id.clone(),
+            level: advisory.level.clone(),
+            message: String::new(),
+            relevant_group_id: None,
+            trace: None,
+        }
+    }
+
+    fn rule(id: &str, min_score: u32, level: AdvisoryLevel) -> EscalationRule {
+        EscalationRule {
+            id: id.to_string(),
+            name: id.to_string(),
+            min_score,
+            level,
+            message_template: String::new(),
+            active: true,
+            send_notification: false,
+            send_tts: false,
+        }
+    }
+
+    #[test]
+    fn risk_score_sums_weights() {
+        let advisories = vec![
+            advisory("a", AdvisoryLevel::Medium, None),
+            advisory("b", AdvisoryLevel::Medium, None),
+            advisory("c", AdvisoryLevel::Low, Some(5)),
+        ];
+        let user_advisories: Vec<ActiveAdvisory> = advisories.iter().map(active).collect();
+        assert_eq!(risk_score(&user_advisories, &advisories), 2 + 2 + 5);
+        // A deleted advisory no longer counts
+        assert_eq!(risk_score(&user_advisories, &advisories[1..]), 2 + 5);
+    }
+
+    #[test]
+    fn highest_reached_rule_applies() {
+        let rules = vec![
+            rule("medium", 2, AdvisoryLevel::Medium),
+            rule("high", 6, AdvisoryLevel::High),
+            rule("high_strict", 8, AdvisoryLevel::High),
+            EscalationRule { active: false, ..rule("inactive", 1, AdvisoryLevel::Maximum) },
+        ];
+        assert_eq!(escalation_rule(&rules, 1), None);
+        assert_eq!(escalation_rule(&rules, 5).map(|r| r.id.as_str()), Some("medium"));
+        assert_eq!(escalation_rule(&rules, 6).map(|r| r.id.as_str()), Some("high"));
+        assert_eq!(escalation_rule(&rules, 10).map(|r| r.id.as_str()), Some("high_strict"));
+    }
+
+    #[test]
+    fn effective_level_is_the_higher_level() {
+        let maximum = active(&advisory("a", AdvisoryLevel::Maximum, None));
+        let low = active(&advisory("b", AdvisoryLevel::Low, None));
+        let high = rule("high", 1, AdvisoryLevel::High);
+        assert_eq!(effective_level(&[low.clone()], Some(&high)), Some(AdvisoryLevel::High));
+        assert_eq!(effective_level(&[low.clone(), maximum], Some(&high)), Some(AdvisoryLevel::Maximum));
+        assert_eq!(effective_level(&[low], None), Some(AdvisoryLevel::Low));
+        assert_eq!(effective_level(&[], None), None);
+    }
+}
@@ -0,0 +1,150 @@
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use tauri::{Manager, Wry};
+
+use crate::{
+    advisories::{escalation::ESCALATION_COOLDOWN_MINUTES, save_advisories, stored_advisories},
+    memory::advisories::AdvisoryMemory,
+    types::advisories::{is_past, AdvisoryCondition},
+};
+
+/// How often to check for expired advisories and watch entries.
+const EXPIRY_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Start the background sweep that deactivates expired advisories, prunes expired
+/// [AdvisoryCondition::IsUntil] entries, and forgets alert times whose cooldown has ended.
+pub fn start_expiry_sweep(app: tauri::AppHandle<Wry>) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(EXPIRY_SWEEP_INTERVAL).await;
+            prune_last_notified(&app);
+            if let Err(e) = sweep_expired_advisories(app.clone()).await {
+                eprintln!("Failed to sweep expired advisories: {}", e);
+            }
+        }
+    });
+}
+
+/// Deactivate expired advisories and prune expired watch entries, then save the changes.
+/// Returns whether anything changed.
+pub async fn sweep_expired_advisories(app: tauri::AppHandle<Wry>) -> Result<bool, String> {
+    let mut adv = match stored_advisories(&app) {
+        Ok(adv) => adv,
+        // Still loading; try again next time
+        Err(_) => return Ok(false),
+    };
+    let mut changed = false;
+    for advisory in adv.iter_mut() {
+        let mut advisory_changed = false;
+        if advisory.active && advisory.is_expired() {
+            println!("Advisory '{}' ({}) has expired, deactivating it", advisory.name, advisory.id);
+            advisory.active = false;
+            advisory_changed = true;
+        }
+        if prune_expired_conditions(&mut advisory.condition) {
+            println!("Pruned expired watch entries from advisory '{}' ({})", advisory.name, advisory.id);
+            advisory_changed = true;
+        }
+        if advisory_changed {
+            advisory.updated_at = chrono::Utc::now().to_rfc3339();
+            changed = true;
+        }
+    }
+    if changed {
+        save_advisories(app, adv).await?;
+    }
+    Ok(changed)
+}
+
+/// Drop [AdvisoryMemory::last_notified] entries whose advisory's cooldown has ended (or that have no cooldown),
+/// since they no longer affect anything. Otherwise they'd build up over a long session.
+fn prune_last_notified(app: &tauri::AppHandle<Wry>) {
+    let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+    let mut advisory_memory = advisory_memory.lock();
+    let now = chrono::Utc::now().timestamp();
+    let AdvisoryMemory { all_advisories, escalation_rules, last_notified, .. } = &mut *advisory_memory;
+    // Entries are keyed by advisory ID, or by escalation rule ID for escalation notices
+    last_notified.retain(|(id, _), last| {
+        let cooldown_minutes = match escalation_rules.iter().any(|rule| rule.id == *id) {
+            true => Some(ESCALATION_COOLDOWN_MINUTES),
+            false => all_advisories.iter().find(|a| a.id == *id).and_then(|a| a.cooldown_minutes).map(i64::from),
+        };
+        cooldown_minutes.is_some_and(|cooldown_minutes| now - *last < cooldown_minutes * 60)
+    });
+}
+
+/// Remove expired [AdvisoryCondition::IsUntil] entries from [AdvisoryCondition::AnyOf] lists.
+/// Entries elsewhere are left alone, since removing them would change what the condition means;
+/// they simply stop matching once they expire.
+/// Returns whether anything was removed.
+fn prune_expired_conditions(condition: &mut AdvisoryCondition) -> bool {
+    match condition {
+        AdvisoryCondition::AnyOf(conditions) => {
+            let before = conditions.len();
+            conditions.retain(|c| !matches!(c, AdvisoryCondition::IsUntil { expires_at, .. } if is_past(expires_at)));
+            let mut changed = conditions.len() != before;
+            for c in conditions.iter_mut() {
+                changed |= prune_expired_conditions(c);
+            }
+            changed
+        }
+        AdvisoryCondition::AllOf(conditions) => {
+            let mut changed = false;
+            for c in conditions.iter_mut() {
+                changed |= prune_expired_conditions(c);
+            }
+            changed
+        }
+        AdvisoryCondition::Not { data } => prune_expired_conditions(data),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAST: &str = "2000-01-01T00:00:00Z";
+    const FUTURE: &str = "2999-01-01T00:00:00Z";
+
+    fn until(id: &str, expires_at: &str) -> AdvisoryCondition {
+        AdvisoryCondition::IsUntil { id: id.to_string(), expires_at: expires_at.to_string() }
+    }
+
+    #[test]
+    fn prunes_expired_entries_from_any_of() {
+        let mut condition = AdvisoryCondition::AnyOf(vec![until("usr_a", PAST), until("usr_b", FUTURE), until("usr_c", PAST)]);
+        assert!(prune_expired_conditions(&mut condition));
+        assert_eq!(condition, AdvisoryCondition::AnyOf(vec![until("usr_b", FUTURE)]));
+        // Nothing left to prune
+        assert!(!prune_expired_conditions(&mut condition));
+    }
+
+    #[test]
+    fn prunes_nested_any_of() {
+        let mut condition = AdvisoryCondition::AllOf(vec![
+            AdvisoryCondition::Not { data: Box::new(AdvisoryCondition::AnyOf(vec![until("usr_a", PAST)])) },
+            AdvisoryCondition::AnyOf(vec![until("usr_b", PAST), until("usr_c", FUTURE)]),
+        ]);
+        assert!(prune_expired_conditions(&mut condition));
+        assert_eq!(
+            condition,
+            AdvisoryCondition::AllOf(vec![
+                AdvisoryCondition::Not { data: Box::new(AdvisoryCondition::AnyOf(Vec::new())) },
+                AdvisoryCondition::AnyOf(vec![until("usr_c", FUTURE)]),
+            ])
+        );
+    }
+
+    #[test]
+    fn keeps_entries_outside_any_of() {
+        // Removing these would change what the condition means
+        let mut condition = AdvisoryCondition::AllOf(vec![until("usr_a", PAST), AdvisoryCondition::Not { data: Box::new(until("usr_b", PAST)) }]);
+        let before = condition.clone();
+        assert!(!prune_expired_conditions(&mut condition));
+        assert_eq!(condition, before);
+        let mut condition = until("usr_a", PAST);
+        assert!(!prune_expired_conditions(&mut condition));
+    }
+}
@@ -0,0 +1,314 @@
+use std::ops::{Deref, DerefMut};
+use parking_lot::Mutex;
+
+use nid::alphabet::Base58Alphabet;
+use nid::Nanoid;
+use tauri::{Emitter, Manager, Wry};
+
+use crate::advisories::actions::{validate_incident_log_path, validate_webhook_url};
+use crate::advisories::stats::schedule_stats_save;
+use crate::advisories::templating::validate_advisory_templates;
+use crate::memory::advisories::AdvisoryMemory;
+use crate::migrations::{QuarantinedRecord, VersionedPayload};
+use crate::types::advisories::{Advisory, AdvisoryAction, AdvisoryCondition, DEFAULT_AVATAR_MIN_CONFIDENCE};
+
+pub mod actions;
+pub mod escalation;
+pub mod expiry;
+pub mod profiles;
+pub mod reevaluation;
+pub mod stats;
+pub mod templating;
+pub mod trusted_users;
+pub mod watchlists;
+
+pub use templating::apply_templating;
+
+pub const ADVISORIES_CONFIG_KEY: &str = "my_advisories";
+
+/// The stored advisory list. Bump `current_version` and add a migration whenever a change to
+/// [Advisory] or its conditions would stop previously-saved advisories from deserializing.
+pub const ADVISORIES_PAYLOAD: VersionedPayload = VersionedPayload {
+    key: ADVISORIES_CONFIG_KEY,
+    current_version: 2,
+    migrations: &[migrate_advisories_v0_to_v1, migrate_advisories_v1_to_v2],
+};
+
+/// v0 advisories were saved before `private` existed.
+fn migrate_advisories_v0_to_v1(mut value: serde_json::Value) -> Result<serde_json::Value, String> {
+    let advisories = value
+        .as_array_mut()
+        .ok_or("Expected a list of advisories")?;
+    for advisory in advisories.iter_mut() {
+        if let Some(advisory) = advisory.as_object_mut() {
+            advisory
+                .entry("private")
+                .or_insert(serde_json::Value::Bool(false));
+        }
+    }
+    Ok(value)
+}
+
+/// v1 `AvatarMayBe` conditions were only an avatar ID, and matched any candidate avatar regardless of confidence.
+fn migrate_advisories_v1_to_v2(mut value: serde_json::Value) -> Result<serde_json::Value, String> {
+    fn migrate_condition(condition: &mut serde_json::Value) {
+        let Some(condition) = condition.as_object_mut() else { return };
+        match condition.get("type").and_then(|t| t.as_str()) {
+            Some("AvatarMayBe") => {
+                if let Some(serde_json::Value::String(avatar_id)) = condition.get("data").cloned() {
+                    condition.insert(
+                        "data".to_string(),
+                        serde_json::json!({ "avatar_id": avatar_id, "min_confidence": DEFAULT_AVATAR_MIN_CONFIDENCE }),
+                    );
+                }
+            }
+            Some("Not") => {
+                if let Some(data) = condition.get_mut("data").and_then(|d| d.get_mut("data")) {
+                    migrate_condition(data);
+                }
+            }
+            Some("AnyOf") | Some("AllOf") => {
+                if let Some(conditions) = condition.get_mut("data").and_then(|d| d.as_array_mut()) {
+                    conditions.iter_mut().for_each(migrate_condition);
+                }
+            }
+            _ => {}
+        }
+    }
+    let advisories = value
+        .as_array_mut()
+        .ok_or("Expected a list of advisories")?;
+    for advisory in advisories.iter_mut() {
+        if let Some(condition) = advisory.get_mut("condition") {
+            migrate_condition(condition);
+        }
+    }
+    Ok(value)
+}
+
+#[tauri::command]
+pub async fn generate_advisory_id() -> String {
+    format!(
+        "vrcmrd_adv_{}",
+        Nanoid::<12, Base58Alphabet>::new().to_string()
+    )
+}
+
+#[tauri::command]
+pub async fn add_advisory(app: tauri::AppHandle<Wry>, advisory: Advisory) -> Result<(), String> {
+    let mut adv = stored_advisories(&app)?;
+    if let Some(_) = adv.iter().position(|v: &Advisory| v.id == advisory.id) {
+        // Can't use this command to update existing advisories
+        return Err("Advisory with this ID already exists".to_string());
+    }
+    validate_advisory(&advisory)?;
+    adv.push(advisory);
+    save_advisories(app, adv).await
+}
+
+#[tauri::command]
+pub async fn get_advisories(app: tauri::AppHandle<Wry>) -> Result<Vec<Advisory>, String> {
+    // gonna move that to the memory plugin later
+    // let adv = match get_config(app.clone(), ADVISORIES_CONFIG_KEY.to_string()).await? {
+    //     Some(existing) => existing,
+    //     None => "[]".to_string(),
+    // };
+    //let adv: Vec<Advisory> = serde_json::from_str(&adv).map_err(|e| e.to_string())?;
+    let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+    let advisory_memory = advisory_memory.lock();
+    return Ok(advisory_memory.deref().all_advisories.clone());
+    //Ok(adv)
+}
+
+pub async fn get_active_advisories(app: tauri::AppHandle<Wry>) -> Result<Vec<Advisory>, String> {
+    let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+    let advisory_memory = advisory_memory.lock();
+    return Ok(advisory_memory.deref().active_advisories.clone());
+}
+
+#[tauri::command]
+pub async fn get_advisory(
+    app: tauri::AppHandle<Wry>,
+    advisory_id: &str,
+) -> Result<Option<Advisory>, String> {
+    // let adv = match get_config(app.clone(), ADVISORIES_CONFIG_KEY.to_string()).await? {
+    //     Some(existing) => existing,
+    //     None => "[]".to_string(),
+    // };
+    // let adv: Vec<Advisory> = serde_json::from_str(&adv).map_err(|e| e.to_string())?;
+    // Ok(adv.into_iter().find(|a| a.id == advisory_id))
+    let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+    let advisory_memory = advisory_memory.lock();
+    Ok(advisory_memory
+        .deref()
+        .all_advisories
+        .iter()
+        .find(|a| a.id == advisory_id)
+        .cloned())
+}
+
+#[tauri::command]
+pub async fn remove_advisory(app: tauri::AppHandle<Wry>, advisory_id: &str) -> Result<(), String> {
+    let mut adv = stored_advisories(&app)?;
+    if let Some(pos) = adv.iter().position(|v: &Advisory| v.id == advisory_id) {
+        adv.remove(pos);
+    } else {
+        return Err("Advisory with this ID does not exist".to_string());
+    }
+    save_advisories(app.clone(), adv).await?;
+    // Stats for deleted advisories are no longer useful
+    let had_stats = app
+        .state::<Mutex<AdvisoryMemory>>()
+        .lock()
+        .stats
+        .remove(advisory_id)
+        .is_some();
+    if had_stats {
+        schedule_stats_save(app);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn update_advisory(app: tauri::AppHandle<Wry>, advisory: Advisory) -> Result<(), String> {
+    validate_advisory(&advisory)?;
+    let mut adv = stored_advisories(&app)?;
+    if let Some(pos) = adv.iter().position(|v: &Advisory| v.id == advisory.id) {
+        adv[pos] = advisory;
+    } else {
+        return Err("Advisory with this ID does not exist".to_string());
+    }
+    save_advisories(app, adv).await
+}
+
+/// Check an advisory's templates, patterns, expiry times and incident log paths before saving it.
+fn validate_advisory(advisory: &Advisory) -> Result<(), String> {
+    validate_advisory_templates(advisory)?;
+    fn validate_timestamp(timestamp: &str) -> Result<(), String> {
+        chrono::DateTime::parse_from_rfc3339(timestamp)
+            .map(|_| ())
+            .map_err(|e| format!("Invalid expiry time \"{}\": {}", timestamp, e))
+    }
+    if let Some(expires_at) = &advisory.expires_at {
+        validate_timestamp(expires_at)?;
+    }
+    for action in advisory.actions.iter() {
+        match action {
+            AdvisoryAction::IncidentLog { path, .. } => validate_incident_log_path(path)?,
+            AdvisoryAction::Webhook { url, .. } => validate_webhook_url(url)?,
+            _ => {}
+        }
+    }
+    fn validate_condition(condition: &AdvisoryCondition) -> Result<(), String> {
+        match condition {
+            AdvisoryCondition::IsUntil { expires_at, .. } => validate_timestamp(expires_at),
+            AdvisoryCondition::AvatarMayBe { min_confidence, .. } if *min_confidence > 100 => {
+                Err(format!("Avatar confidence must be at most 100, got {}", min_confidence))
+            }
+            AdvisoryCondition::BioMatches(pattern) => regex::RegexBuilder::new(pattern)
+                .case_insensitive(true)
+                .build()
+                .map(|_| ())
+                .map_err(|e| format!("Invalid bio pattern \"{}\": {}", pattern, e)),
+            AdvisoryCondition::Not { data } => validate_condition(data),
+            AdvisoryCondition::AnyOf(conditions) | AdvisoryCondition::AllOf(conditions) => {
+                conditions.iter().try_for_each(validate_condition)
+            }
+            _ => Ok(()),
+        }
+    }
+    validate_condition(&advisory.condition)
+}
+
+/// Get the advisories that were quarantined because they couldn't be loaded,
+/// i.e. after an incompatible change to [Advisory] or [crate::types::advisories::AdvisoryCondition].
+#[tauri::command]
+pub async fn get_quarantined_advisories(app: tauri::AppHandle<Wry>) -> Result<Vec<QuarantinedRecord>, String> {
+    ADVISORIES_PAYLOAD.quarantined(app).await
+}
+
+/// Get the advisories from memory, to be modified and passed to [save_advisories].
+/// Fails if the advisories haven't been loaded from the config store yet, so that
+/// a command run during startup can't overwrite them with an empty list.
+pub fn stored_advisories(app: &tauri::AppHandle<Wry>) -> Result<Vec<Advisory>, String> {
+    let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+    let advisory_memory = advisory_memory.lock();
+    if !advisory_memory.loaded {
+        return Err("Advisories are still loading".to_string());
+    }
+    Ok(advisory_memory.all_advisories.clone())
+}
+
+/// Replace all advisories, in memory and in the config store, and notify the UI.
+pub async fn save_advisories(app: tauri::AppHandle<Wry>, adv: Vec<Advisory>) -> Result<(), String> {
+    {
+        app.state::<Mutex<AdvisoryMemory>>()
+            .lock()
+            .deref_mut()
+            .set(adv.clone());
+    }
+    let adv = serde_json::to_value(&adv).map_err(|e| e.to_string())?;
+    ADVISORIES_PAYLOAD.save(app.clone(), &adv).await?;
+    app.emit("vrcmrd:advisories_updated", {})
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_known_advisory_tags(app: tauri::AppHandle<Wry>) -> Result<Vec<String>, String> {
+    let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+    let advisory_memory = advisory_memory.lock();
+    let mut tags = advisory_memory
+        .all_advisories
+        .iter()
+        .flat_map(|a| a.tags.clone())
+        .collect::<Vec<String>>();
+    tags.sort();
+    tags.dedup();
+    Ok(tags)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn v1_avatar_may_be_gets_default_confidence() {
+        let v1 = json!([{
+            "condition": { "type": "AllOf", "data": [
+                { "type": "AvatarMayBe", "data": "avtr_a" },
+                { "type": "Not", "data": { "data": { "type": "AvatarMayBe", "data": "avtr_b" } } },
+                { "type": "AnyOf", "data": [{ "type": "AvatarMayBe", "data": "avtr_c" }] },
+                { "type": "UsernameContains", "data": "avtr_d" },
+            ] },
+        }]);
+        let migrated = migrate_advisories_v1_to_v2(v1).unwrap();
+        let avatar = |id: &str| json!({ "type": "AvatarMayBe", "data": { "avatar_id": id, "min_confidence": DEFAULT_AVATAR_MIN_CONFIDENCE } });
+        assert_eq!(
+            migrated[0]["condition"]["data"],
+            json!([
+                avatar("avtr_a"),
+                { "type": "Not", "data": { "data": avatar("avtr_b") } },
+                { "type": "AnyOf", "data": [avatar("avtr_c")] },
+                { "type": "UsernameContains", "data": "avtr_d" },
+            ])
+        );
+    }
+
+    #[test]
+    fn v2_avatar_may_be_is_left_alone() {
+        let v2 = json!([{ "condition": { "type": "AvatarMayBe", "data": { "avatar_id": "avtr_a", "min_confidence": 20 } } }]);
+        assert_eq!(migrate_advisories_v1_to_v2(v2.clone()).unwrap(), v2);
+    }
+
+    #[test]
+    fn v0_advisories_migrate_to_current() {
+        let v0 = json!([{ "id": "a", "condition": { "type": "AvatarMayBe", "data": "avtr_a" } }]);
+        let migrated = ADVISORIES_PAYLOAD.migrate(v0, 0).unwrap();
+        assert_eq!(migrated[0]["private"], json!(false));
+        assert_eq!(migrated[0]["condition"]["data"]["avatar_id"], json!("avtr_a"));
+        // Not a list
+        assert!(ADVISORIES_PAYLOAD.migrate(json!({}), 0).is_err());
+    }
+}
@@ -0,0 +1,97 @@
+use parking_lot::Mutex;
+use tauri::{Emitter, Manager, Runtime, Wry};
+
+use crate::{
+    memory::{advisories::AdvisoryMemory, instance::InstanceStateMutex},
+    migrations::VersionedPayload,
+    types::advisories::AdvisoryProfile,
+};
+
+/// Advisory profiles, limiting advisories to the instances they're meant for.
+/// This payload was introduced after schema versioning, so it starts at version 0.
+pub const ADVISORY_PROFILES_PAYLOAD: VersionedPayload = VersionedPayload {
+    key: "advisory_profiles",
+    current_version: 0,
+    migrations: &[],
+};
+
+#[tauri::command]
+pub async fn get_advisory_profiles(app: tauri::AppHandle<Wry>) -> Result<Vec<AdvisoryProfile>, String> {
+    let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+    let advisory_memory = advisory_memory.lock();
+    Ok(advisory_memory.profiles.clone())
+}
+
+/// Get the profile for the current instance, if any.
+#[tauri::command]
+pub async fn get_active_advisory_profile(app: tauri::AppHandle<Wry>) -> Result<Option<AdvisoryProfile>, String> {
+    let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+    let advisory_memory = advisory_memory.lock();
+    Ok(advisory_memory.active_profile().cloned())
+}
+
+/// Replace all advisory profiles, and re-select the profile for the current instance.
+#[tauri::command]
+pub async fn set_advisory_profiles(
+    app: tauri::AppHandle<Wry>,
+    profiles: Vec<AdvisoryProfile>,
+) -> Result<(), String> {
+    for profile in profiles.iter() {
+        if profile.name.trim().is_empty() {
+            return Err("Every profile needs a name".to_string());
+        }
+    }
+    {
+        let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+        let mut advisory_memory = advisory_memory.lock();
+        if !advisory_memory.profiles_loaded {
+            return Err("Advisory profiles are still loading".to_string());
+        }
+        advisory_memory.profiles = profiles.clone();
+    }
+    let value = serde_json::to_value(&profiles).map_err(|e| e.to_string())?;
+    ADVISORY_PROFILES_PAYLOAD.save(app.clone(), &value).await?;
+    switch_advisory_profile(app.clone());
+    app.emit("vrcmrd:advisory_profiles_updated", {})
+        .map_err(|e| e.to_string())
+}
+
+pub async fn load_advisory_profiles<R: Runtime>(app: tauri::AppHandle<R>) -> Result<Vec<AdvisoryProfile>, String> {
+    ADVISORY_PROFILES_PAYLOAD.load_records(app).await
+}
+
+/// Select the first active profile with a rule matching the current instance, and limit the active advisories to it.
+/// Emits `vrcmrd:advisory_profile_changed` with the new profile (or `null`) if it changed.
+pub fn switch_advisory_profile<R: Runtime>(app: tauri::AppHandle<R>) {
+    let instance = {
+        let instance_state = app.state::<InstanceStateMutex>();
+        let instance_state = instance_state.lock();
+        instance_state.id_info.clone()
+    };
+    let (changed, profile) = {
+        let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+        let mut advisory_memory = advisory_memory.lock();
+        let profile = instance.as_ref().and_then(|instance| {
+            advisory_memory
+                .profiles
+                .iter()
+                .find(|p| p.active && p.rules.iter().any(|rule| rule.matches(instance)))
+                .cloned()
+        });
+        let profile_id = profile.as_ref().map(|p| p.id.clone());
+        let changed = advisory_memory.active_profile_id != profile_id;
+        advisory_memory.active_profile_id = profile_id;
+        // Refresh even if the profile is the same, since its contents may have changed
+        advisory_memory.refresh_active_advisories();
+        (changed, profile)
+    };
+    if changed {
+        match &profile {
+            Some(profile) => println!("Switched to advisory profile '{}' ({})", profile.name, profile.id),
+            None => println!("No advisory profile matches this instance; using all active advisories"),
+        }
+        if let Err(e) = app.emit("vrcmrd:advisory_profile_changed", profile) {
+            eprintln!("Failed to emit advisory profile change: {}", e);
+        }
+    }
+}
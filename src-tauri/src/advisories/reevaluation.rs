@@ -0,0 +1,179 @@
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use tauri::{Emitter, Listener, Manager, Wry};
+
+use crate::{
+    advisories::escalation::apply_escalation,
+    api::user::AdvisoryTrigger,
+    memory::{advisories::AdvisoryMemory, instance::current_instance_id, users::Users},
+    notices::publish_notice,
+    settings::get_config,
+    types::{
+        advisories::{ActiveAdvisory, AdvisoryLevel, Notice},
+        VrcMrdUser,
+    },
+};
+
+/// How often to re-evaluate advisories for users in the instance, in seconds. 0 turns it off.
+pub const REEVALUATION_INTERVAL_CONFIG_KEY: &str = "reevaluation_interval_seconds";
+pub const DEFAULT_REEVALUATION_INTERVAL_SECONDS: u64 = 60;
+/// What to notify when users in the instance newly match after the advisories change:
+/// `0` for nothing, `1` for a notice per match (the default), or `2` for one digest notice.
+pub const ADVISORY_CHANGE_NOTICES_CONFIG_KEY: &str = "advisory_change_notices";
+
+/// Start re-evaluating advisories for users in the instance, both periodically and whenever the advisories change.
+/// Advisories are otherwise only evaluated on join and on avatar switch, so time-dependent conditions
+/// (i.e. [crate::types::advisories::AdvisoryCondition::TimeInInstanceAtLeastMinutes]) rely on this.
+pub fn start_reevaluation(app: tauri::AppHandle<Wry>) {
+    for event in ["vrcmrd:advisories_updated", "vrcmrd:advisory_profiles_updated"] {
+        let app_clone = app.clone();
+        app.listen(event, move |_| {
+            let app = app_clone.clone();
+            tauri::async_runtime::spawn(async move {
+                reevaluate_after_change(app).await;
+            });
+        });
+    }
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let interval = reevaluation_interval(app.clone()).await;
+            if interval == 0 {
+                // Turned off; check again later in case it's turned back on
+                tokio::time::sleep(Duration::from_secs(DEFAULT_REEVALUATION_INTERVAL_SECONDS)).await;
+                continue;
+            }
+            tokio::time::sleep(Duration::from_secs(interval)).await;
+            reevaluate_present_users(app.clone(), AdvisoryTrigger::Reevaluated);
+        }
+    });
+}
+
+async fn reevaluation_interval(app: tauri::AppHandle<Wry>) -> u64 {
+    get_config(app, REEVALUATION_INTERVAL_CONFIG_KEY.to_string())
+        .await
+        .ok()
+        .flatten()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_REEVALUATION_INTERVAL_SECONDS)
+}
+
+/// Re-run advisories for every user in the instance after the advisories changed, so new rules apply
+/// to users who are already here. Depending on [ADVISORY_CHANGE_NOTICES_CONFIG_KEY], new matches are
+/// notified one by one, not at all, or in a single digest notice. Only matches notified one by one
+/// run the advisory's actions and count towards its stats.
+async fn reevaluate_after_change(app: tauri::AppHandle<Wry>) {
+    let mode = get_config(app.clone(), ADVISORY_CHANGE_NOTICES_CONFIG_KEY.to_string())
+        .await
+        .ok()
+        .flatten()
+        .unwrap_or("1".to_string());
+    let matches = reevaluate_present_users(app.clone(), AdvisoryTrigger::AdvisoriesChanged { notify: mode == "1" });
+    if mode != "2" || matches.is_empty() {
+        return;
+    }
+    let instance_id = current_instance_id(&app);
+    if let Err(e) = publish_notice(app, digest_notice(&matches), instance_id) {
+        eprintln!("Failed to publish advisory change digest: {}", e);
+    }
+}
+
+/// One notice summarising every new match, as (username, advisory) pairs.
+fn digest_notice(matches: &[(String, ActiveAdvisory)]) -> Notice {
+    let mut usernames: Vec<&String> = matches.iter().map(|(username, _)| username).collect();
+    usernames.dedup();
+    let level = matches
+        .iter()
+        .map(|(_, advisory)| advisory.level.clone())
+        .max_by_key(|level| level.clone() as u8)
+        .unwrap_or(AdvisoryLevel::None);
+    Notice {
+        title: Some(match usernames.len() {
+            1 => "Advisories changed: 1 user now matches".to_string(),
+            count => format!("Advisories changed: {} users now match", count),
+        }),
+        message: matches
+            .iter()
+            .map(|(username, advisory)| format!("{}: {}", username, advisory.message))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        level,
+        created_at: Some(chrono::Utc::now().to_rfc3339()),
+        ..Default::default()
+    }
+}
+
+/// Re-run advisories for every user in the instance whose info has loaded.
+/// Returns the advisories each user newly matched, with their username.
+pub fn reevaluate_present_users(app: tauri::AppHandle<Wry>, trigger: AdvisoryTrigger) -> Vec<(String, ActiveAdvisory)> {
+    let present: Vec<(String, String)> = {
+        let users_state = app.state::<Mutex<Users>>();
+        let users_state = users_state.lock();
+        // Users still waiting for their info are evaluated once it arrives, in `update_from`
+        users_state
+            .inner
+            .iter()
+            .filter(|u| u.is_in_instance() && u.info_loaded)
+            .map(|u| (u.id.clone(), u.username.clone()))
+            .collect()
+    };
+    let mut matches = Vec::new();
+    for (user_id, username) in present {
+        for advisory in reevaluate_user(app.clone(), &user_id, trigger) {
+            matches.push((username.clone(), advisory));
+        }
+    }
+    matches
+}
+
+/// Re-run advisories for a user in the instance, i.e. after their avatar was looked up.
+/// Updates (and emits) the user if their advisories changed, and returns the advisories they newly matched.
+/// Does nothing until the user's info has loaded, since their advisories are evaluated then anyway.
+pub fn reevaluate_user(app: tauri::AppHandle<Wry>, user_id: &str, trigger: AdvisoryTrigger) -> Vec<ActiveAdvisory> {
+    {
+        let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+        if !advisory_memory.lock().loaded {
+            return Vec::new();
+        }
+    }
+    // Evaluate on a copy, since publishing notices needs the user list
+    let user: Option<VrcMrdUser> = {
+        let users_state = app.state::<Mutex<Users>>();
+        let users_state = users_state.lock();
+        users_state.inner.iter().find(|u| u.id == user_id && u.is_in_instance() && u.info_loaded).cloned()
+    };
+    let Some(mut user) = user else {
+        return Vec::new();
+    };
+    let advisories = user.with_advisories(app.clone(), trigger);
+    if advisories == user.advisories {
+        return Vec::new();
+    }
+    let added: Vec<ActiveAdvisory> = advisories
+        .iter()
+        .filter(|a| !user.advisories.iter().any(|existing| existing.id == a.id))
+        .cloned()
+        .collect();
+    user.advisories = advisories;
+    apply_escalation(app.clone(), &mut user);
+    let updated = {
+        let users_state = app.state::<Mutex<Users>>();
+        let mut users_state = users_state.lock();
+        // Only copy the advisory fields, so changes made while evaluating aren't lost
+        users_state
+            .inner
+            .iter_mut()
+            .find(|u| u.id == user.id && u.is_in_instance())
+            .map(|existing| {
+                existing.advisories = user.advisories.clone();
+                existing.risk_score = user.risk_score;
+                existing.effective_level = user.effective_level.clone();
+                existing.escalated_by = user.escalated_by.clone();
+                existing.clone()
+            })
+    };
+    if let Some(updated) = updated {
+        let _ = app.emit("vrcmrd:update-user", updated);
+    }
+    added
+}
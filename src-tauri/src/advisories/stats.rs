@@ -0,0 +1,157 @@
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use parking_lot::Mutex;
+use tauri::{Manager, Runtime, Wry};
+
+use crate::{
+    api::user::AdvisoryTrigger,
+    memory::advisories::AdvisoryMemory,
+    migrations::VersionedPayload,
+    types::advisories::AdvisoryStats,
+};
+
+/// Advisory hit statistics, keyed by advisory ID.
+/// This payload was introduced after schema versioning, so it starts at version 0.
+pub const ADVISORY_STATS_PAYLOAD: VersionedPayload = VersionedPayload {
+    key: "advisory_stats",
+    current_version: 0,
+    migrations: &[],
+};
+
+/// How many user IDs to keep per advisory, so stats don't grow without limit.
+pub const MAX_MATCHED_USER_IDS: usize = 100;
+
+/// Set while a save is scheduled, so a burst of matches (i.e. a raid) only saves once.
+static SAVE_PENDING: AtomicBool = AtomicBool::new(false);
+
+/// Get the hit statistics for every advisory that has matched at least once.
+#[tauri::command]
+pub async fn get_advisory_stats(
+    app: tauri::AppHandle<Wry>,
+) -> Result<HashMap<String, AdvisoryStats>, String> {
+    let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+    let advisory_memory = advisory_memory.lock();
+    Ok(advisory_memory.stats.clone())
+}
+
+/// Load the stored statistics. Statistics that can't be read are discarded, since they can be rebuilt.
+pub async fn load_advisory_stats<R: Runtime>(
+    app: tauri::AppHandle<R>,
+) -> Result<HashMap<String, AdvisoryStats>, String> {
+    match ADVISORY_STATS_PAYLOAD.load(app).await? {
+        Some(value) => {
+            let mut stats: HashMap<String, AdvisoryStats> = serde_json::from_value(value).unwrap_or_else(|e| {
+                eprintln!("Failed to read advisory stats, starting over: {}", e);
+                HashMap::new()
+            });
+            // Stats saved before the cap only have the ID list
+            for stats in stats.values_mut() {
+                if stats.matched_user_count == 0 {
+                    stats.matched_user_count = stats.matched_user_ids.len() as u32;
+                }
+                let excess = stats.matched_user_ids.len().saturating_sub(MAX_MATCHED_USER_IDS);
+                stats.matched_user_ids.drain(..excess);
+            }
+            Ok(stats)
+        }
+        None => Ok(HashMap::new()),
+    }
+}
+
+/// Record that an advisory was newly applied to a user.
+pub fn record_advisory_match(
+    app: tauri::AppHandle<Wry>,
+    advisory_id: &str,
+    user_id: &str,
+    trigger: &AdvisoryTrigger,
+) {
+    {
+        let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+        let mut advisory_memory = advisory_memory.lock();
+        let stats = advisory_memory
+            .stats
+            .entry(advisory_id.to_string())
+            .or_default();
+        let now = chrono::Utc::now().to_rfc3339();
+        stats.total_matches += 1;
+        match trigger {
+            AdvisoryTrigger::JoinLeave => stats.join_leave_matches += 1,
+            AdvisoryTrigger::AvatarSwitched => stats.avatar_switched_matches += 1,
+            AdvisoryTrigger::Reevaluated | AdvisoryTrigger::AdvisoriesChanged { .. } => stats.reevaluation_matches += 1,
+        }
+        add_matched_user(stats, user_id);
+        if stats.first_fired_at.is_none() {
+            stats.first_fired_at = Some(now.clone());
+        }
+        stats.last_fired_at = Some(now);
+    }
+    schedule_stats_save(app);
+}
+
+/// Add a user to the most recently matched users, counting them if they're new.
+fn add_matched_user(stats: &mut AdvisoryStats, user_id: &str) {
+    match stats.matched_user_ids.iter().position(|id| id == user_id) {
+        Some(index) => {
+            // Move it to the end, so the most recent users are kept
+            let id = stats.matched_user_ids.remove(index);
+            stats.matched_user_ids.push(id);
+        }
+        None => {
+            stats.matched_user_count += 1;
+            stats.matched_user_ids.push(user_id.to_string());
+            if stats.matched_user_ids.len() > MAX_MATCHED_USER_IDS {
+                stats.matched_user_ids.remove(0);
+            }
+        }
+    }
+}
+
+/// Merge the matches recorded before the stored statistics were loaded into them.
+pub fn merge_advisory_stats(stored: &mut HashMap<String, AdvisoryStats>, recorded: HashMap<String, AdvisoryStats>) {
+    for (advisory_id, recorded) in recorded {
+        let stats = stored.entry(advisory_id).or_default();
+        stats.total_matches += recorded.total_matches;
+        stats.join_leave_matches += recorded.join_leave_matches;
+        stats.avatar_switched_matches += recorded.avatar_switched_matches;
+        stats.reevaluation_matches += recorded.reevaluation_matches;
+        for user_id in recorded.matched_user_ids.iter() {
+            add_matched_user(stats, user_id);
+        }
+        stats.first_fired_at = stats.first_fired_at.take().or(recorded.first_fired_at);
+        stats.last_fired_at = recorded.last_fired_at.or(stats.last_fired_at.take());
+    }
+}
+
+/// Save the statistics after a short delay. Does nothing if a save is already scheduled,
+/// or if the stored statistics haven't loaded yet (they're saved once the recorded matches are merged in).
+pub fn schedule_stats_save<R: Runtime>(app: tauri::AppHandle<R>) {
+    if SAVE_PENDING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        SAVE_PENDING.store(false, Ordering::SeqCst);
+        let stats = {
+            let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+            let advisory_memory = advisory_memory.lock();
+            if !advisory_memory.stats_loaded {
+                return;
+            }
+            advisory_memory.stats.clone()
+        };
+        let stats = match serde_json::to_value(&stats) {
+            Ok(stats) => stats,
+            Err(e) => {
+                eprintln!("Failed to serialize advisory stats: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = ADVISORY_STATS_PAYLOAD.save(app.clone(), &stats).await {
+            eprintln!("Failed to save advisory stats: {}", e);
+        }
+    });
+}
@@ -0,0 +1,460 @@
+use std::{collections::HashMap, sync::LazyLock};
+
+use regex::Regex;
+
+use crate::types::advisories::{Advisory, AdvisoryAction, TemplateVariable};
+
+/// Variables that can be used in advisory templates, with a description for the editor.
+/// The last field is whether the variable is filled for every user (when the information is known),
+/// rather than only by specific conditions.
+pub const KNOWN_TEMPLATE_VARIABLES: &[(&str, &str, bool)] = &[
+    ("username", "The user's display name.", true),
+    ("user_id", "The user's ID (usr_***).", true),
+    ("trust_rank", "The user's trust rank, i.e. \"New User\".", true),
+    ("platform", "The platform the user is on: pc, android or ios.", true),
+    ("pronouns", "The user's pronouns.", true),
+    ("status", "The user's status description.", true),
+    ("perf_rank", "The performance rank of the user's avatar, i.e. \"Very Poor\".", true),
+    ("join_time", "When the user joined, as a unix timestamp. Use with the relative_time filter.", true),
+    ("account_age_days", "The user's account age in days.", true),
+    ("instance_name", "The name of the current instance (or its world).", true),
+    ("avatar_name", "The name of the user's avatar.", true),
+    ("avatar_creator", "The ID of the user's avatar's creator.", true),
+    ("group_name", "The name of the relevant group (with group conditions).", false),
+    ("group_id", "The ID of the relevant group (with group conditions).", false),
+    ("group_owner", "The ID of the relevant group's owner (with group conditions).", false),
+    ("group_count", "How many groups the user is in, or how many matched (with group count conditions).", true),
+    ("avatar_confidence", "How sure we are (out of 100) that the user is wearing the avatar (with avatar conditions).", false),
+    ("bio_match", "The part of the user's bio (or the bio link) that matched (with bio conditions).", false),
+    ("moderated_at", "When the user was kicked or banned, as a unix timestamp (with recently kicked/banned conditions).", false),
+    ("risk_score", "The user's risk score (in escalation rules).", false),
+    ("advisory_count", "How many advisories the user matches (in escalation rules).", false),
+];
+
+/// `{{:...:}}`
+static TAG_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s)\{\{:(.*?):\}\}").unwrap());
+static VARIABLE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[a-zA-Z0-9_]+$").unwrap());
+
+#[tauri::command]
+pub fn get_template_variables() -> Vec<TemplateVariable> {
+    KNOWN_TEMPLATE_VARIABLES
+        .iter()
+        .map(|(name, description, always_available)| TemplateVariable {
+            name: name.to_string(),
+            description: description.to_string(),
+            always_available: *always_available,
+        })
+        .collect()
+}
+
+enum Filter {
+    Upper,
+    Truncate(usize),
+    RelativeTime,
+}
+
+struct Expression {
+    variable: String,
+    filters: Vec<Filter>,
+    default: Option<String>,
+}
+
+enum Node {
+    Text(String),
+    Variable(Expression),
+    If {
+        negate: bool,
+        variable: String,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+}
+
+struct IfFrame {
+    negate: bool,
+    variable: String,
+    then: Vec<Node>,
+    otherwise: Option<Vec<Node>>,
+}
+
+impl IfFrame {
+    fn push(&mut self, node: Node) {
+        match &mut self.otherwise {
+            Some(otherwise) => otherwise.push(node),
+            None => self.then.push(node),
+        }
+    }
+    fn into_node(self) -> Node {
+        Node::If {
+            negate: self.negate,
+            variable: self.variable,
+            then: self.then,
+            otherwise: self.otherwise.unwrap_or_default(),
+        }
+    }
+}
+
+/// Parse a template. Parsing never fails, so a broken template still renders something;
+/// problems are collected and returned alongside, for [validate_template].
+///
+/// Supported syntax:
+/// - `{{:variable:}}`, `{{:variable||default:}}`
+/// - Filters, applied in order: `{{:variable|upper:}}`, `{{:variable|truncate:20:}}`, `{{:join_time|relative_time:}}`
+/// - Conditionals: `{{:#if variable:}}...{{:else:}}...{{:/if:}}`, or `{{:#if !variable:}}` to negate.
+///   A variable is truthy if it's set and not empty.
+fn parse(template: &str) -> (Vec<Node>, Vec<String>) {
+    let mut errors = Vec::new();
+    let mut root = Vec::new();
+    let mut stack: Vec<IfFrame> = Vec::new();
+    fn push(stack: &mut Vec<IfFrame>, root: &mut Vec<Node>, node: Node) {
+        match stack.last_mut() {
+            Some(frame) => frame.push(node),
+            None => root.push(node),
+        }
+    }
+    let mut last = 0;
+    for cap in TAG_REGEX.captures_iter(template) {
+        let whole = cap.get(0).unwrap();
+        if whole.start() > last {
+            push(&mut stack, &mut root, Node::Text(template[last..whole.start()].to_string()));
+        }
+        last = whole.end();
+        let inner = cap[1].trim();
+        if let Some(condition) = inner.strip_prefix("#if ") {
+            let condition = condition.trim();
+            let (negate, variable) = match condition.strip_prefix('!') {
+                Some(variable) => (true, variable.trim()),
+                None => (false, condition),
+            };
+            if !VARIABLE_REGEX.is_match(variable) {
+                errors.push(format!("Invalid variable name in conditional: \"{}\"", variable));
+            }
+            stack.push(IfFrame {
+                negate,
+                variable: variable.to_string(),
+                then: Vec::new(),
+                otherwise: None,
+            });
+        } else if inner == "else" {
+            match stack.last_mut() {
+                Some(frame) if frame.otherwise.is_none() => frame.otherwise = Some(Vec::new()),
+                _ => {
+                    errors.push("{{:else:}} without a matching {{:#if:}}".to_string());
+                    push(&mut stack, &mut root, Node::Text(whole.as_str().to_string()));
+                }
+            }
+        } else if inner == "/if" {
+            match stack.pop() {
+                Some(frame) => push(&mut stack, &mut root, frame.into_node()),
+                None => {
+                    errors.push("{{:/if:}} without a matching {{:#if:}}".to_string());
+                    push(&mut stack, &mut root, Node::Text(whole.as_str().to_string()));
+                }
+            }
+        } else {
+            match parse_expression(inner) {
+                Ok(expression) => push(&mut stack, &mut root, Node::Variable(expression)),
+                Err(e) => {
+                    errors.push(e);
+                    push(&mut stack, &mut root, Node::Text(whole.as_str().to_string()));
+                }
+            }
+        }
+    }
+    if last < template.len() {
+        push(&mut stack, &mut root, Node::Text(template[last..].to_string()));
+    }
+    while let Some(frame) = stack.pop() {
+        errors.push(format!("{{{{:#if {}:}}}} is never closed with {{{{:/if:}}}}", frame.variable));
+        push(&mut stack, &mut root, frame.into_node());
+    }
+    (root, errors)
+}
+
+fn parse_expression(inner: &str) -> Result<Expression, String> {
+    let (expression, default) = match inner.split_once("||") {
+        Some((expression, default)) => (expression, Some(default.to_string())),
+        None => (inner, None),
+    };
+    let mut parts = expression.split('|');
+    let variable = parts.next().unwrap_or_default().trim();
+    if !VARIABLE_REGEX.is_match(variable) {
+        return Err(format!("Invalid variable name: \"{}\"", variable));
+    }
+    let mut filters = Vec::new();
+    for filter in parts {
+        let filter = filter.trim();
+        let (name, argument) = match filter.split_once(':') {
+            Some((name, argument)) => (name.trim(), Some(argument.trim())),
+            None => (filter, None),
+        };
+        filters.push(match (name, argument) {
+            ("upper", None) => Filter::Upper,
+            ("relative_time", None) => Filter::RelativeTime,
+            ("truncate", Some(length)) => Filter::Truncate(
+                length
+                    .parse()
+                    .map_err(|_| format!("Invalid length for truncate filter: \"{}\"", length))?,
+            ),
+            ("truncate", None) => return Err("The truncate filter needs a length, i.e. truncate:20".to_string()),
+            _ => return Err(format!("Unknown filter: \"{}\"", filter)),
+        });
+    }
+    Ok(Expression {
+        variable: variable.to_string(),
+        filters,
+        default,
+    })
+}
+
+/// Render parsed nodes. `escape` is applied to each variable's final value (after filters), i.e. for JSON.
+fn render(nodes: &[Node], variables: &HashMap<&str, String>, escape: fn(&str) -> String, output: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => output.push_str(text),
+            Node::Variable(expression) => {
+                let mut value = match (variables.get(expression.variable.as_str()), &expression.default) {
+                    (Some(value), _) => value.clone(),
+                    (None, Some(default)) => default.clone(),
+                    (None, None) => String::new(),
+                };
+                for filter in expression.filters.iter() {
+                    value = match filter {
+                        Filter::Upper => value.to_uppercase(),
+                        Filter::Truncate(length) => {
+                            if value.chars().count() > *length {
+                                value.chars().take(*length).collect::<String>() + "…"
+                            } else {
+                                value
+                            }
+                        }
+                        Filter::RelativeTime => relative_time(&value).unwrap_or(value),
+                    };
+                }
+                output.push_str(&escape(&value));
+            }
+            Node::If {
+                negate,
+                variable,
+                then,
+                otherwise,
+            } => {
+                let truthy = variables
+                    .get(variable.as_str())
+                    .is_some_and(|value| !value.is_empty());
+                if truthy != *negate {
+                    render(then, variables, escape, output);
+                } else {
+                    render(otherwise, variables, escape, output);
+                }
+            }
+        }
+    }
+}
+
+/// Format a unix timestamp (in seconds) or RFC 3339 timestamp relative to now, i.e. "5 minutes ago".
+fn relative_time(value: &str) -> Option<String> {
+    let timestamp = match value.trim().parse::<i64>() {
+        Ok(timestamp) => timestamp,
+        Err(_) => chrono::DateTime::parse_from_rfc3339(value.trim()).ok()?.timestamp(),
+    };
+    let difference = chrono::Utc::now().timestamp() - timestamp;
+    let seconds = difference.abs();
+    let (amount, unit) = if seconds < 60 {
+        return Some("just now".to_string());
+    } else if seconds < 60 * 60 {
+        (seconds / 60, "minute")
+    } else if seconds < 60 * 60 * 24 {
+        (seconds / (60 * 60), "hour")
+    } else {
+        (seconds / (60 * 60 * 24), "day")
+    };
+    let plural = if amount == 1 { "" } else { "s" };
+    Some(if difference >= 0 {
+        format!("{} {}{} ago", amount, unit, plural)
+    } else {
+        format!("in {} {}{}", amount, unit, plural)
+    })
+}
+
+/// Render a template with the given variables. See [parse] for the supported syntax.
+/// Undefined variables render as their default, or as nothing.
+pub fn apply_templating(template: &str, variables: &HashMap<&str, String>) -> String {
+    let (nodes, _) = parse(template);
+    let mut output = String::new();
+    render(&nodes, variables, |value| value.to_string(), &mut output);
+    output
+}
+
+/// Like [apply_templating], but each variable is escaped for use inside a JSON string.
+/// Most variables are set by the user being flagged (i.e. their username or bio), so without this
+/// they could break the payload, or add their own fields to it.
+pub fn apply_json_templating(template: &str, variables: &HashMap<&str, String>) -> String {
+    let (nodes, _) = parse(template);
+    let mut output = String::new();
+    render(&nodes, variables, json_escape, &mut output);
+    output
+}
+
+fn json_escape(value: &str) -> String {
+    let quoted = serde_json::Value::String(value.to_string()).to_string();
+    quoted[1..quoted.len() - 1].to_string()
+}
+
+/// Check that a webhook payload template renders to valid JSON, with every variable set to `0`
+/// (which is valid both inside a string and as a number).
+fn validate_json_template(template: &str) -> Result<(), String> {
+    let variables: HashMap<&str, String> = KNOWN_TEMPLATE_VARIABLES
+        .iter()
+        .map(|(name, _, _)| (*name, "0".to_string()))
+        .collect();
+    serde_json::from_str::<serde_json::Value>(&apply_json_templating(template, &variables))
+        .map(|_| ())
+        .map_err(|e| format!("The payload isn't valid JSON: {}", e))
+}
+
+/// Check a template for syntax errors and unknown variables.
+pub fn validate_template(template: &str) -> Result<(), String> {
+    let (nodes, mut errors) = parse(template);
+    fn collect_variables<'a>(nodes: &'a [Node], variables: &mut Vec<&'a str>) {
+        for node in nodes {
+            match node {
+                Node::Text(_) => {}
+                Node::Variable(expression) => variables.push(expression.variable.as_str()),
+                Node::If {
+                    variable,
+                    then,
+                    otherwise,
+                    ..
+                } => {
+                    variables.push(variable.as_str());
+                    collect_variables(then, variables);
+                    collect_variables(otherwise, variables);
+                }
+            }
+        }
+    }
+    let mut variables = Vec::new();
+    collect_variables(&nodes, &mut variables);
+    for variable in variables {
+        if !KNOWN_TEMPLATE_VARIABLES.iter().any(|(name, _, _)| *name == variable)
+            && VARIABLE_REGEX.is_match(variable)
+        {
+            errors.push(format!("Unknown variable: \"{}\"", variable));
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        errors.dedup();
+        Err(errors.join("; "))
+    }
+}
+
+/// Validate the message template and every action template of an advisory.
+pub fn validate_advisory_templates(advisory: &Advisory) -> Result<(), String> {
+    validate_template(&advisory.message_template).map_err(|e| format!("Message template: {}", e))?;
+    for (index, action) in advisory.actions.iter().enumerate() {
+        let templates = match action {
+            AdvisoryAction::Webhook { payload_template, .. } => vec![payload_template],
+            AdvisoryAction::IncidentLog { line_template, .. } => vec![line_template],
+            AdvisoryAction::Osc { argument_template, .. } => vec![argument_template],
+            AdvisoryAction::AddToWatchlist(_) | AdvisoryAction::CopyUserId => vec![],
+        };
+        for template in templates {
+            validate_template(template).map_err(|e| format!("Action {}: {}", index + 1, e))?;
+        }
+        if let AdvisoryAction::Webhook { payload_template, .. } = action {
+            validate_json_template(payload_template).map_err(|e| format!("Action {}: {}", index + 1, e))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render_with(template: &str, variables: &[(&'static str, &str)]) -> String {
+        let variables: HashMap<&str, String> = variables.iter().map(|(k, v)| (*k, v.to_string())).collect();
+        apply_templating(template, &variables)
+    }
+
+    #[test]
+    fn nested_conditionals() {
+        let template = "{{:#if username:}}A{{:#if !status:}}B{{:else:}}C{{:/if:}}{{:/if:}}D";
+        assert_eq!(render_with(template, &[("username", "x")]), "ABD");
+        assert_eq!(render_with(template, &[("username", "x"), ("status", "busy")]), "ACD");
+        assert_eq!(render_with(template, &[("status", "busy")]), "D");
+        // Empty values are falsy
+        assert_eq!(render_with(template, &[("username", "")]), "D");
+        assert!(validate_template(template).is_ok());
+    }
+
+    #[test]
+    fn unclosed_if() {
+        let template = "{{:#if username:}}hello";
+        let error = validate_template(template).unwrap_err();
+        assert!(error.contains("never closed"), "{}", error);
+        // Still renders as if it was closed at the end
+        assert_eq!(render_with(template, &[("username", "x")]), "hello");
+        assert_eq!(render_with(template, &[]), "");
+    }
+
+    #[test]
+    fn stray_else_and_endif() {
+        assert!(validate_template("a{{:else:}}b").unwrap_err().contains("without a matching"));
+        assert!(validate_template("a{{:/if:}}b").unwrap_err().contains("without a matching"));
+        assert_eq!(render_with("a{{:/if:}}b", &[]), "a{{:/if:}}b");
+    }
+
+    #[test]
+    fn truncate_multi_byte() {
+        assert_eq!(render_with("{{:username|truncate:3:}}", &[("username", "日本語テキスト")]), "日本語…");
+        assert_eq!(render_with("{{:username|truncate:2:}}", &[("username", "🦊🐺🐱")]), "🦊🐺…");
+        assert_eq!(render_with("{{:username|truncate:10:}}", &[("username", "ünïcödé")]), "ünïcödé");
+    }
+
+    #[test]
+    fn filters_and_defaults() {
+        assert_eq!(render_with("{{:username|upper|truncate:2:}}", &[("username", "abc")]), "AB…");
+        assert_eq!(render_with("{{:status||nothing:}}", &[]), "nothing");
+        assert_eq!(render_with("{{:status||nothing:}}", &[("status", "busy")]), "busy");
+    }
+
+    #[test]
+    fn unknown_filters() {
+        let error = validate_template("{{:username|lower:}}").unwrap_err();
+        assert!(error.contains("Unknown filter"), "{}", error);
+        assert!(validate_template("{{:username|truncate:}}").is_err());
+        assert!(validate_template("{{:username|truncate:abc:}}").is_err());
+        // Broken tags are left as they are
+        assert_eq!(render_with("{{:username|lower:}}", &[("username", "x")]), "{{:username|lower:}}");
+    }
+
+    #[test]
+    fn unknown_variables() {
+        let error = validate_template("{{:not_a_variable:}}").unwrap_err();
+        assert!(error.contains("Unknown variable"), "{}", error);
+        assert!(validate_template("{{:#if not_a_variable:}}x{{:/if:}}").is_err());
+    }
+
+    #[test]
+    fn json_templating_escapes_variables() {
+        let template = r#"{"content": "{{:username:}} {{:status|upper:}}"}"#;
+        let variables: HashMap<&str, String> = [
+            ("username", r#"evil", "everyone": "@everyone"#.to_string()),
+            ("status", "line\nbreak \\".to_string()),
+        ]
+        .into_iter()
+        .collect();
+        let payload = apply_json_templating(template, &variables);
+        let value: serde_json::Value = serde_json::from_str(&payload).unwrap();
+        let object = value.as_object().unwrap();
+        assert_eq!(object.len(), 1);
+        assert_eq!(object["content"], r#"evil", "everyone": "@everyone LINE
+BREAK \"#);
+        assert!(validate_json_template(template).is_ok());
+        assert!(validate_json_template(r#"{"content": {{:username:}}"#).is_err());
+    }
+}
@@ -0,0 +1,102 @@
+use std::sync::LazyLock;
+
+use parking_lot::Mutex;
+use regex::Regex;
+use tauri::{Emitter, Manager, Runtime, Wry};
+use tauri_plugin_dialog::DialogExt;
+
+use crate::{memory::advisories::AdvisoryMemory, migrations::VersionedPayload};
+
+/// User IDs that the host's moderators already know and trust, i.e. friends of staff.
+/// Used by [crate::types::advisories::AdvisoryCondition::IsStaffTrusted].
+/// This payload was introduced after schema versioning, so it starts at version 0.
+pub const STAFF_TRUSTED_USERS_PAYLOAD: VersionedPayload = VersionedPayload {
+    key: "staff_trusted_users",
+    current_version: 0,
+    migrations: &[],
+};
+
+/// User IDs are either `usr_` followed by a UUID, or (for old accounts) 10 letters and digits on their own.
+static USER_ID_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\busr_[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}\b|\b[0-9A-Za-z]{10}\b").unwrap()
+});
+
+/// Whether a match of [USER_ID_REGEX] is likely a user ID. Legacy IDs need both letters and digits,
+/// so 10-letter words and 10-digit numbers (i.e. timestamps) in the file aren't taken for them.
+fn is_user_id(found: &str) -> bool {
+    found.starts_with("usr_")
+        || (found.chars().any(|c| c.is_ascii_digit()) && found.chars().any(|c| c.is_ascii_alphabetic()))
+}
+
+#[tauri::command]
+pub async fn get_staff_trusted_users(app: tauri::AppHandle<Wry>) -> Result<Vec<String>, String> {
+    let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+    let advisory_memory = advisory_memory.lock();
+    Ok(advisory_memory.staff_trusted_user_ids.clone())
+}
+
+/// Replace the list of moderator-trusted user IDs.
+#[tauri::command]
+pub async fn set_staff_trusted_users(
+    app: tauri::AppHandle<Wry>,
+    user_ids: Vec<String>,
+) -> Result<(), String> {
+    save_staff_trusted_users(app, user_ids).await
+}
+
+/// Ask the host for a file and add every user ID in it to the moderator-trusted list.
+/// Any format works (one ID per line, a JSON export, a CSV...), since IDs are found by pattern.
+/// Returns how many new IDs were added, or `None` if no file was picked.
+#[tauri::command]
+pub async fn import_staff_trusted_users(app: tauri::AppHandle<Wry>) -> Result<Option<usize>, String> {
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    app.dialog()
+        .file()
+        .set_title("Import moderator-trusted users")
+        .add_filter("User lists", &["txt", "json", "csv"])
+        .pick_file(move |path| {
+            let _ = sender.send(path);
+        });
+    let Some(path) = receiver.await.map_err(|e| e.to_string())? else {
+        return Ok(None);
+    };
+    let path = path.into_path().map_err(|e| e.to_string())?;
+    let contents = tokio::fs::read_to_string(&path).await.map_err(|e| e.to_string())?;
+    let mut user_ids = {
+        let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+        let advisory_memory = advisory_memory.lock();
+        advisory_memory.staff_trusted_user_ids.clone()
+    };
+    let before = user_ids.len();
+    // IDs are kept as they are, since legacy IDs are case-sensitive
+    for found in USER_ID_REGEX.find_iter(&contents).filter(|found| is_user_id(found.as_str())) {
+        let user_id = found.as_str().to_string();
+        if !user_ids.contains(&user_id) {
+            user_ids.push(user_id);
+        }
+    }
+    let added = user_ids.len() - before;
+    println!("Imported {} moderator-trusted users from {}", added, path.display());
+    save_staff_trusted_users(app, user_ids).await?;
+    Ok(Some(added))
+}
+
+pub async fn load_staff_trusted_users<R: Runtime>(app: tauri::AppHandle<R>) -> Result<Vec<String>, String> {
+    STAFF_TRUSTED_USERS_PAYLOAD.load_records(app).await
+}
+
+async fn save_staff_trusted_users(app: tauri::AppHandle<Wry>, mut user_ids: Vec<String>) -> Result<(), String> {
+    user_ids.retain(|id| !id.trim().is_empty());
+    {
+        let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+        let mut advisory_memory = advisory_memory.lock();
+        if !advisory_memory.staff_trusted_users_loaded {
+            return Err("Moderator-trusted users are still loading".to_string());
+        }
+        advisory_memory.staff_trusted_user_ids = user_ids.clone();
+    }
+    let value = serde_json::to_value(&user_ids).map_err(|e| e.to_string())?;
+    STAFF_TRUSTED_USERS_PAYLOAD.save(app.clone(), &value).await?;
+    app.emit("vrcmrd:staff_trusted_users_updated", {})
+        .map_err(|e| e.to_string())
+}
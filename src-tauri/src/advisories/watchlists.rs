@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+
+use parking_lot::Mutex;
+use tauri::{Emitter, Manager, Runtime, Wry};
+
+use crate::{memory::advisories::AdvisoryMemory, migrations::VersionedPayload};
+
+/// Named lists of user IDs, i.e. filled by [crate::types::advisories::AdvisoryAction::AddToWatchlist].
+/// This payload was introduced after schema versioning, so it starts at version 0.
+pub const WATCHLISTS_PAYLOAD: VersionedPayload = VersionedPayload {
+    key: "watchlists",
+    current_version: 0,
+    migrations: &[],
+};
+
+/// Get all watchlists, keyed by name.
+#[tauri::command]
+pub async fn get_watchlists(
+    app: tauri::AppHandle<Wry>,
+) -> Result<HashMap<String, Vec<String>>, String> {
+    let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+    let advisory_memory = advisory_memory.lock();
+    Ok(advisory_memory.watchlists.clone())
+}
+
+pub async fn load_watchlists<R: Runtime>(
+    app: tauri::AppHandle<R>,
+) -> Result<HashMap<String, Vec<String>>, String> {
+    match WATCHLISTS_PAYLOAD.load(app).await? {
+        Some(value) => serde_json::from_value(value).map_err(|e| e.to_string()),
+        None => Ok(HashMap::new()),
+    }
+}
+
+/// Add a user to the named watchlist, creating it if needed.
+/// Returns false if the user was already on it.
+pub fn add_to_watchlist(
+    watchlists: &mut HashMap<String, Vec<String>>,
+    name: &str,
+    user_id: &str,
+) -> bool {
+    let watchlist = watchlists.entry(name.to_string()).or_default();
+    if watchlist.iter().any(|id| id == user_id) {
+        return false;
+    }
+    watchlist.push(user_id.to_string());
+    true
+}
+
+/// Save the watchlists from memory and notify the UI.
+/// Fails if the stored watchlists haven't loaded yet; users added before then are saved once they have.
+pub async fn save_watchlists<R: Runtime>(app: tauri::AppHandle<R>) -> Result<(), String> {
+    let watchlists = {
+        let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+        let advisory_memory = advisory_memory.lock();
+        if !advisory_memory.watchlists_loaded {
+            return Err("Watchlists are still loading".to_string());
+        }
+        advisory_memory.watchlists.clone()
+    };
+    let value = serde_json::to_value(&watchlists).map_err(|e| e.to_string())?;
+    WATCHLISTS_PAYLOAD.save(app.clone(), &value).await?;
+    app.emit("vrcmrd:watchlists_updated", {})
+        .map_err(|e| e.to_string())
+}
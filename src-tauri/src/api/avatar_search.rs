@@ -4,7 +4,7 @@ use parking_lot::Mutex;
 use tauri::{AppHandle, Emitter, Manager};
 
 /// Searches for avatars using third-party APIs (same as VRCX uses).
-use crate::{api::{VRCHAT_API_USERAGENT, VrchatApiStateMutex}, memory::users::Users, types::{VrcMrdUser, avatar::{AvatarBundleFileMetadata, GetWorstRank, PerfRank, VrcxAvatarSearchResult}}};
+use crate::{advisories::reevaluation::reevaluate_user, api::{VRCHAT_API_USERAGENT, VrchatApiStateMutex, user::AdvisoryTrigger}, memory::{advisories::AdvisoryMemory, users::Users}, types::{VrcMrdUser, avatar::{AvatarBundleFileMetadata, AvatarCandidate, GetWorstRank, PerfRank, VrcxAvatarSearchResult}}};
 
 // TODO: use a setting to get the list, and use this hardcoded list as the default instead
 const AVATAR_API_BASE_URLS: [&str; 3] = [
@@ -24,6 +24,15 @@ const AVATAR_API_BASE_URLS_SUPPORTING_AUTHORID: [&str; 1] = [
     //"https://vrcx.vrcdb.com/avatars/Avatar/VRCX",
 ];
 
+/// Confidence (out of 100) that the user is wearing an avatar found by its image file.
+const CONFIDENCE_IMAGE_FILE: u8 = 100;
+/// An avatar with the same name, found by searching the image file owner's avatars.
+const CONFIDENCE_AUTHOR_NAME_MATCH: u8 = 90;
+/// Another avatar by the same creator, with a different name.
+const CONFIDENCE_SAME_AUTHOR: u8 = 20;
+/// An avatar found through an observed bundle file; scaled by how well the bundle's name matches.
+const CONFIDENCE_BUNDLE_FILE: u8 = 80;
+
 const AVATAR_SEARCH_SKIP_FILES: [&str; 1] = [
     "file_0e8c4e32-7444-44ea-ade4-313c010d4bae", // default robot avatar. Probably a VRC+ user.
 ];
@@ -166,7 +175,9 @@ pub async fn search_avatar_by_author(owner_id: &str, query: Option<&str>, client
     Err("All avatar search attempts failed".to_string())
 }
 
-pub fn update_avatar(user: VrcMrdUser, app: AppHandle) {
+/// Look up a user's avatar in the background, then re-run their avatar advisories.
+/// `trigger` is what caused the lookup, i.e. the user joining or switching avatars, and is used for the notice and stats.
+pub fn update_avatar(user: VrcMrdUser, app: AppHandle, trigger: AdvisoryTrigger) {
     let app_clone = app.clone();
     // TODO: set up a unique username+tags+aviname disk cache
     tauri::async_runtime::spawn(async move {
@@ -198,7 +209,7 @@ pub fn update_avatar(user: VrcMrdUser, app: AppHandle) {
                 Ok(Some(avatar_result)) => {
                     let avatar_name = avatar_result.name.clone().unwrap_or_else(|| "Unknown Avatar".to_string());
                     println!("Found avatar '{}' for user '{}'", avatar_name, user.username);
-                    let (returned_avatar_id, has_perf_info) = update_avatar_from_search(&user, avatar_result, app.clone());
+                    let (returned_avatar_id, has_perf_info) = update_avatar_from_search(&user, avatar_result, app.clone(), CONFIDENCE_IMAGE_FILE);
                     if has_perf_info {
                         found_performance_info = true;
                     }
@@ -232,12 +243,17 @@ pub fn update_avatar(user: VrcMrdUser, app: AppHandle) {
                                     eprintln!("No avatars found for author ID '{}' when updating avatar for user '{}'", owner_id, user.username);
                                 } else {
                                     println!("Found {} possible avatars for author ID '{}' when updating avatar for user '{}'", results.len(), owner_id, user.username);
+                                    // Any of the author's avatars may be the one, even if the name doesn't match
+                                    add_avatar_candidates(&user, results.iter().map(|r| AvatarCandidate {
+                                        avatar_id: r.id.clone(),
+                                        confidence: CONFIDENCE_SAME_AUTHOR,
+                                    }).collect(), app.clone());
                                     for avatar_result in results {
                                         if avatar_result.name.is_some() && avatar_result.name.as_ref().unwrap().clone() == user.avatar_name {
                                             let avatar_name = avatar_result.name.clone().unwrap();
                                             let author = avatar_result.author_id.clone();
                                             println!("Found matching avatar '{}' for user '{}' by author ID search", avatar_name, user.username);
-                                            let (returned_avatar_id, has_perf_info) = update_avatar_from_search(&user, avatar_result, app.clone());
+                                            let (returned_avatar_id, has_perf_info) = update_avatar_from_search(&user, avatar_result, app.clone(), CONFIDENCE_AUTHOR_NAME_MATCH);
                                             if let Some(author) = author {
                                                 let users_state = app_clone.state::<Mutex<Users>>();
                                                 let mut users_state = users_state.lock();
@@ -279,6 +295,7 @@ pub fn update_avatar(user: VrcMrdUser, app: AppHandle) {
             instance_state.settled
         };
         let mut file_meta: Option<AvatarBundleFileMetadata> = None;
+        let mut file_meta_coef: f32 = 0.0;
         if avatar_id.is_none() && settled {
             // TODO: use found-file metadata for avatar bundles, get the owner, look it up again by name (to hopefully more reliably get the cross-platform perf rank)
             let mut attempts = 0;
@@ -321,7 +338,10 @@ pub fn update_avatar(user: VrcMrdUser, app: AppHandle) {
                         }
                     }
                 }
-                filtered.first().cloned().and_then(|f| Some(f.1))
+                filtered.first().cloned().map(|f| {
+                    file_meta_coef = f.0;
+                    f.1
+                })
             };
             if file_meta.is_none() {
                 eprintln!("No matching file metadata found for avatar '{}' worn by user '{}'", user.clone().avatar_name, user.clone().username);
@@ -355,7 +375,8 @@ pub fn update_avatar(user: VrcMrdUser, app: AppHandle) {
                                         }
                                     }
                                     // Update the user in the user list with the new avatar data
-                                    let (returned_avatar_id, has_perf_info) = update_avatar_from_search(&user, avatar_result, app.clone());
+                                    let confidence = (CONFIDENCE_BUNDLE_FILE as f32 * file_meta_coef.clamp(0.0, 1.0)).round() as u8;
+                                    let (returned_avatar_id, has_perf_info) = update_avatar_from_search(&user, avatar_result, app.clone(), confidence);
                                     if has_perf_info {
                                         found_performance_info = true;
                                     }
@@ -442,10 +463,19 @@ pub fn update_avatar(user: VrcMrdUser, app: AppHandle) {
                 Err(e) => eprintln!("Failed to get avatar info from VRChat API for avatar ID '{}' when looking up performance info for user '{}': {}", avatar_id, user.clone().username, e),
             }
         }
+        // Avatar conditions can only be evaluated once the lookup is done
+        let needs_avatar = {
+            let advisory_memory = app_clone.state::<Mutex<AdvisoryMemory>>();
+            let advisory_memory = advisory_memory.lock();
+            advisory_memory.data_requirements.avatar
+        };
+        if needs_avatar {
+            reevaluate_user(app_clone.clone(), &user.id, trigger);
+        }
     });
 }
 
-fn update_avatar_from_search(user: &VrcMrdUser, result: VrcxAvatarSearchResult, app: AppHandle) -> (Option<String>, bool) {
+fn update_avatar_from_search(user: &VrcMrdUser, result: VrcxAvatarSearchResult, app: AppHandle, confidence: u8) -> (Option<String>, bool) {
     let mut found_performance_info = false;
     // Update the user in the user list with the new avatar data
     let users_state = app.state::<Mutex<Users>>();
@@ -461,12 +491,14 @@ fn update_avatar_from_search(user: &VrcMrdUser, result: VrcxAvatarSearchResult,
         .find(|u| u.username == user.username)
     {
         user.avatar_id = Some(result.id.clone());
+        push_avatar_candidate(user, AvatarCandidate { avatar_id: result.id.clone(), confidence });
         // if let Some(name) = result.name.clone() {
         //     user.avatar_name = name;
         // }
         if let Some(author) = result.author_id.clone() {
             user.avatar_creator = Some(author);
         }
+        user.avatar_release_status = result.release_status.clone();
         if let Some(performance) = result.performance.clone() {
             user.perf_rank = performance.get_worst_rank();
             found_performance_info = true;
@@ -477,4 +509,28 @@ fn update_avatar_from_search(user: &VrcMrdUser, result: VrcxAvatarSearchResult,
         eprintln!("Failed to emit update-user event: {}", e);
     };
     (user.avatar_id.clone(), found_performance_info)
-}
\ No newline at end of file
+}
+
+/// Add avatars the user may be wearing, i.e. other avatars by the same creator.
+fn add_avatar_candidates(user: &VrcMrdUser, candidates: Vec<AvatarCandidate>, app: AppHandle) {
+    let users_state = app.state::<Mutex<Users>>();
+    let mut users_state = users_state.lock();
+    if let Some(user) = users_state
+        .inner
+        .iter_mut()
+        .find(|u| u.username == user.username)
+    {
+        for candidate in candidates {
+            push_avatar_candidate(user, candidate);
+        }
+    }
+}
+
+/// Add a candidate avatar, keeping the highest confidence if it's already there.
+fn push_avatar_candidate(user: &mut VrcMrdUser, candidate: AvatarCandidate) {
+    if let Some(existing) = user.avatar_candidates.iter_mut().find(|c| c.avatar_id == candidate.avatar_id) {
+        existing.confidence = existing.confidence.max(candidate.confidence);
+    } else {
+        user.avatar_candidates.push(candidate);
+    }
+}
@@ -7,8 +7,8 @@ use tauri::{AppHandle, Emitter, Manager};
 use vrchatapi::models::LimitedUserInstance;
 
 use crate::{
-    advisories::apply_templating, api::avatar_search::update_avatar, memory::{advisories::AdvisoryMemory, users::Users}, notices::publish_notice, types::{
-        PartialGroup, VrcMrdUser, advisories::{ActiveAdvisory, AdvisoryCondition, AdvisoryGroupCondition, make_notice}, user::{CommonUser, GetTrustRank}
+    advisories::{actions::run_advisory_actions, apply_templating, escalation::apply_escalation, stats::record_advisory_match}, api::avatar_search::{get_file_id_from_image_url, update_avatar}, memory::{advisories::AdvisoryMemory, group_details::attach_group_details, group_roles::{GroupRoles, fetch_hosting_group_roles}, instance::current_instance_id, moderation::{ModerationHistory, ModerationKind}, users::Users}, notices::publish_notice, types::{
+        PartialGroup, VrcMrdUser, advisories::{ActiveAdvisory, AdvisoryCondition, AdvisoryGroupCondition, ConditionResult, is_past, make_notice}, user::{CommonUser, GetTrustRank, UserFlags}
     }
 };
 
@@ -35,18 +35,20 @@ pub async fn query_user_info(app: AppHandle, user_id: &str) {
         eprintln!("User not found in list for ID: {}", user_id);
         return;
     }
+    // Only fetch what the active advisories need. The profile (with the bio) is always fetched above, and
+    // the hosting group's roles below, since the user list shows group staff either way.
+    let requirements = {
+        let advisories = app.state::<Mutex<AdvisoryMemory>>();
+        let advisories = advisories.lock();
+        advisories.deref().data_requirements
+    };
     let groups = {
-        let has_group_membership_advisory = {
-            let advisories = app.state::<Mutex<AdvisoryMemory>>();
-            let advisories = advisories.lock();
-            advisories.deref().has_group_membership_advisory
-        };
         let base_groups = base_user.clone().unwrap().groups.clone();
-        if !base_groups.is_empty() {
+        if base_user.as_ref().is_some_and(|u| u.groups_known) {
             // TODO: use a cache epoch to ignore the cache after a refresh (i.e. if someone joins/leaves a group and rejoins)
             // If we already have groups, use them (this avoids an unnecessary API call and also ensures we have group names for advisories)
-            base_groups
-        } else if has_group_membership_advisory {
+            Some(base_groups)
+        } else if requirements.groups {
             println!("Fetching group list for user ID: {}", user_id);
             let group_list = try_request!(app.clone(), |config| {
                 vrchatapi::apis::users_api::get_user_groups(config, user_id)
@@ -59,21 +61,31 @@ pub async fn query_user_info(app: AppHandle, user_id: &str) {
                         user_id,
                         groups.len()
                     );
-                    groups.iter().map(|v| PartialGroup {
+                    Some(groups.iter().map(|v| PartialGroup {
                         id: v.clone().group_id.unwrap_or_default(),
                         name: v.clone().name.unwrap_or(v.clone().group_id.unwrap_or_default()),
                         owner_id: v.clone().owner_id.unwrap_or_default(),
-                    }).collect()
+                        member_count: v.member_count.and_then(|count| u32::try_from(count).ok()),
+                        details: None,
+                    }).collect())
                 }
                 _ => {
                     eprintln!("Failed to fetch group list for user ID: {}", user_id);
-                    Vec::new()
+                    None
                 }
             }
         } else {
-            Vec::new()
+            None
+        }
+    };
+    let groups = match groups {
+        Some(mut groups) if requirements.group_details => {
+            attach_group_details(app.clone(), &mut groups).await;
+            Some(groups)
         }
+        groups => groups,
     };
+    fetch_hosting_group_roles(app.clone(), user_id).await;
     let app = app.clone();
     match user {
         Ok(Some(user_info)) => {
@@ -100,7 +112,7 @@ pub async fn query_user_info(app: AppHandle, user_id: &str) {
             let _ = app.emit("vrcmrd:update-user", vrcmrd_user.clone());
             if vrcmrd_user.avatar_id.is_none() {
                 let user = vrcmrd_user.clone();
-                update_avatar(user, app.clone());
+                update_avatar(user, app.clone(), AdvisoryTrigger::JoinLeave);
             }
         }
         Ok(None) => {
@@ -120,9 +132,15 @@ pub fn thread_query_user_info(app: AppHandle, user_id: &str) {
     });
 }
 
+#[derive(Clone, Copy)]
 pub enum AdvisoryTrigger {
     JoinLeave,
     AvatarSwitched,
+    /// A periodic re-evaluation.
+    Reevaluated,
+    /// The advisories (or advisory profiles) changed. Notices are only published, matches counted and actions run
+    /// if `notify` is set, so a bulk pass over the instance can be silent or summarised instead.
+    AdvisoriesChanged { notify: bool },
 }
 
 impl VrcMrdUser {
@@ -130,10 +148,16 @@ impl VrcMrdUser {
         &mut self,
         app: AppHandle,
         other: &CommonUser,
-        groups: Vec<PartialGroup>,
+        groups: Option<Vec<PartialGroup>>,
     ) -> &mut Self {
         let user: LimitedUserInstance = other.into();
         self.age_verified = user.age_verified;
+        self.is_friend = user.is_friend;
+        self.is_group_staff = {
+            let group_roles = app.state::<Mutex<GroupRoles>>();
+            let group_roles = group_roles.lock();
+            group_roles.is_staff(&self.id).unwrap_or(false)
+        };
         if let Some(date_joined) = user.date_joined.clone() {
             self.account_created = NaiveDate::parse_from_str(&date_joined, "%Y-%m-%d")
                 .ok()
@@ -151,7 +175,11 @@ impl VrcMrdUser {
         self.avatar_images = avatar_images;
         self.pronouns = user.pronouns.clone().into();
         self.status = user.status_description.clone().into();
+        self.bio = user.bio.clone().filter(|bio| !bio.is_empty());
+        self.bio_links = user.bio_links.clone().unwrap_or_default();
         self.trust_rank = Some(user.trust_rank());
+        self.tags = user.tags.clone();
+        self.flags = UserFlags::from_tags(&user.tags);
         self.platform = {
             let platform = user.last_platform;
             if platform == "standalonewindows" {
@@ -164,9 +192,15 @@ impl VrcMrdUser {
                 None
             }
         };
-        self.groups = groups;
+        // Keep the groups we already know about if they weren't fetched this time
+        if let Some(groups) = groups {
+            self.groups = groups;
+            self.groups_known = true;
+        }
+        self.info_loaded = true;
         self.advisories =
             self.with_advisories(app.clone(), AdvisoryTrigger::JoinLeave);
+        apply_escalation(app.clone(), self);
         self
     }
     pub fn with_advisories(
@@ -176,21 +210,39 @@ impl VrcMrdUser {
     ) -> Vec<ActiveAdvisory> {
         let mut advisories = self.advisories.clone();
         //let user: LimitedUserInstance = user.into();
-        let active_advisories = app
-            .state::<Mutex<AdvisoryMemory>>()
-            .lock()
-            .deref()
-            .active_advisories
-            .clone();
+        let (active_advisories, staff_trusted) = {
+            let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+            let advisory_memory = advisory_memory.lock();
+            (
+                advisory_memory.active_advisories.clone(),
+                advisory_memory.staff_trusted_user_ids.contains(&self.id),
+            )
+        };
+        let base_templates = self.template_variables(&app);
+        let instance_id = current_instance_id(&app);
         for advisory in active_advisories.iter() {
             let relevant_group_id: RefCell<Option<String>> = RefCell::new(None);
-            let templates = RefCell::new(HashMap::new());
-            let keep = RefCell::new(Vec::new());
-            templates
-                .borrow_mut()
-                    .insert("username", self.username.clone());
-            if advisory.condition.evaluate(&|condition| match condition {
+            let templates = RefCell::new(base_templates.clone());
+            // Set when the result comes from keeping the advisory as it is, for the trace
+            let kept = RefCell::new(false);
+            let kept_existing = || {
+                *kept.borrow_mut() = true;
+                advisories.iter().any(|a| a.id == advisory.id)
+            };
+            let recently_moderated = |kind: ModerationKind, hours: u32| {
+                let history = app.state::<Mutex<ModerationHistory>>();
+                let history = history.lock();
+                match history.latest_within_hours(&self.id, kind, hours) {
+                    Some(event) => {
+                        templates.borrow_mut().insert("moderated_at", event.at.to_string());
+                        true
+                    }
+                    None => false,
+                }
+            };
+            let leaf = |condition: AdvisoryCondition| match condition {
                 AdvisoryCondition::Is(id) => self.id == id,
+                AdvisoryCondition::IsUntil { id, expires_at } => self.id == id && !is_past(&expires_at),
                 AdvisoryCondition::UsernameContains(string) => self
                     .username
                 .to_lowercase()
@@ -207,17 +259,84 @@ impl VrcMrdUser {
                     .unwrap_or_default()
                     .to_lowercase()
                     .contains(&string.to_lowercase()),
+                AdvisoryCondition::BioContains(string) => {
+                    let matches = self
+                        .bio
+                        .clone()
+                        .unwrap_or_default()
+                        .to_lowercase()
+                        .contains(&string.to_lowercase());
+                    if matches {
+                        templates.borrow_mut().insert("bio_match", string.clone());
+                    }
+                    matches
+                }
+                AdvisoryCondition::BioMatches(pattern) => {
+                    let regex = match regex::RegexBuilder::new(&pattern).case_insensitive(true).build() {
+                        Ok(regex) => regex,
+                        Err(e) => {
+                            eprintln!("Invalid bio pattern in advisory {}: {}", advisory.id, e);
+                            return false;
+                        }
+                    };
+                    match regex.find(self.bio.as_deref().unwrap_or_default()) {
+                        Some(found) => {
+                            templates.borrow_mut().insert("bio_match", found.as_str().to_string());
+                            true
+                        }
+                        None => false,
+                    }
+                }
+                AdvisoryCondition::BioLinkDomainIs(domain) => {
+                    let domain = domain.trim().trim_start_matches("www.").to_lowercase();
+                    let link = self.bio_links.iter().find(|link| {
+                        link_domain(link).is_some_and(|host| {
+                            host == domain || host.ends_with(&format!(".{}", domain))
+                        })
+                    });
+                    if let Some(link) = link {
+                        templates.borrow_mut().insert("bio_match", link.clone());
+                        return true;
+                    }
+                    false
+                }
                 AdvisoryCondition::AgeNotVerified => !self.age_verified,
+                AdvisoryCondition::RecentlyKickedWithinHours(hours) => recently_moderated(ModerationKind::Kick, hours),
+                AdvisoryCondition::RecentlyBannedWithinHours(hours) => recently_moderated(ModerationKind::Ban, hours),
+                AdvisoryCondition::IsFriend => self.is_friend,
+                AdvisoryCondition::IsStaffTrusted => staff_trusted,
+                AdvisoryCondition::InWatchlist(name) => {
+                    let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+                    let advisory_memory = advisory_memory.lock();
+                    advisory_memory.watchlists.get(&name).is_some_and(|watchlist| watchlist.contains(&self.id))
+                }
+                AdvisoryCondition::HasGroupRole { group_id, role_id } => {
+                    let group_roles = app.state::<Mutex<GroupRoles>>();
+                    let group_roles = group_roles.lock();
+                    match group_roles.role_ids(&group_id, &self.id) {
+                        Some(_) if role_id.is_empty() => match group_roles.is_staff(&self.id) {
+                            Some(is_staff) => is_staff,
+                            // The group's management roles failed to fetch
+                            None => kept_existing(),
+                        },
+                        Some(role_ids) => role_ids.contains(&role_id),
+                        // Roles aren't known (i.e. not in the group's instance), so keep the advisory as it is
+                        None => kept_existing(),
+                    }
+                }
                 AdvisoryCondition::TrustRankAtMost(trust_rank) => self.trust_rank.is_some() && self.trust_rank.clone().unwrap() <= trust_rank,
+                AdvisoryCondition::HasTag(tag) => self.tags.iter().any(|t| t.eq_ignore_ascii_case(&tag)),
+                AdvisoryCondition::TagPrefix(prefix) => {
+                    let prefix = prefix.to_lowercase();
+                    self.tags.iter().any(|t| t.to_lowercase().starts_with(&prefix))
+                }
                 AdvisoryCondition::PlatformIs(platform) => {
                         self.platform.as_deref().unwrap_or("").to_lowercase() == platform.to_lowercase()
                 }
                 AdvisoryCondition::IsGroupMember(group_id) => {
-                    if self.groups.is_empty() && advisories.iter().any(|a| a.id == advisory.id) {
-                        // if the advisory is already active and groups are not available, assume the user is
-                        // still in the group.
-                        keep.borrow_mut().push(advisory.id.clone());
-                        return true;
+                    if !self.groups_known {
+                        // Without the group list, we can't tell; keep the advisory as it is
+                        return kept_existing();
                     }
                         for group in self.groups.iter() {
                             if group.id != group_id {
@@ -228,43 +347,65 @@ impl VrcMrdUser {
                             templates
                                 .borrow_mut()
                                 .insert("group_name", group.name.clone());
+                            templates
+                                .borrow_mut()
+                                .insert("group_owner", group.owner_id.clone());
                             return true;
                         }
                     return false;
                 }
                 AdvisoryCondition::GroupCondition(condition) => {
-                    if self.groups.is_empty() && advisories.iter().any(|a| a.id == advisory.id) {
-                        // if the advisory is already active and groups are not available, assume the user is
-                        // still in the group.
-                        keep.borrow_mut().push(advisory.id.clone());
-                        return true;
-                    }
-                    fn evaluator(group: &PartialGroup, condition: AdvisoryGroupCondition, advisories: &Vec<ActiveAdvisory>, advisory_id: &String) -> bool {
-                        match condition {
-                            AdvisoryGroupCondition::NameContains(string) => group.name.to_lowercase().contains(&string.to_lowercase()),
-                            AdvisoryGroupCondition::OwnerIs(owner_id) => group.owner_id == owner_id,
-                            AdvisoryGroupCondition::Id(id) => group.id == id,
-                            _ => {
-                                println!(
-                                    "Advisory group condition not implemented in user advisory evaluation: {:?}",
-                                    condition
-                                );
-                                advisories.iter().any(|a| a.id == *advisory_id)
-                            }
-                        }
+                    if !self.groups_known {
+                        // Without the group list, we can't tell; keep the advisory as it is
+                        return kept_existing();
                     }
+                    let mut unknown = false;
                     for group in self.groups.iter() {
-                        if condition.evaluate(&|condition: AdvisoryGroupCondition| evaluator(group, condition.clone(), &advisories, &advisory.id)) {
+                        let matched = group_matches(group, &condition);
+                        unknown |= matched.is_none();
+                        if matched == Some(true) {
                             // Apply group details to variables
                             *relevant_group_id.borrow_mut() = Some(group.id.clone());
                             templates.borrow_mut().insert("group_id", group.id.clone());
                             templates
                                 .borrow_mut()
                                 .insert("group_name", group.name.clone());
+                            templates
+                                .borrow_mut()
+                                .insert("group_owner", group.owner_id.clone());
                             return true;
                         }
                     }
-                    return false;
+                    // A group whose details aren't known might still match
+                    if unknown {
+                        return kept_existing();
+                    }
+                    false
+                }
+                AdvisoryCondition::GroupCountAtLeast { condition, count } => {
+                    if !self.groups_known {
+                        // Without the group list, we can't count; keep the advisory as it is
+                        return kept_existing();
+                    }
+                    let results: Vec<Option<bool>> = self.groups.iter().map(|group| group_matches(group, &condition)).collect();
+                    let matching = results.iter().filter(|matched| **matched == Some(true)).count();
+                    let unknown = results.iter().filter(|matched| matched.is_none()).count();
+                    templates.borrow_mut().insert("group_count", matching.to_string());
+                    if matching >= count as usize {
+                        true
+                    } else if matching + unknown >= count as usize {
+                        // Groups whose details aren't known could still make up the count
+                        kept_existing()
+                    } else {
+                        false
+                    }
+                }
+                AdvisoryCondition::TimeInInstanceAtLeastMinutes(minutes) => {
+                    if !self.is_in_instance() {
+                        return false;
+                    }
+                    let minutes_in_instance = (chrono::Utc::now().timestamp() - self.join_time) / 60;
+                    minutes_in_instance >= minutes as i64
                 }
                 AdvisoryCondition::AccountAgeAtMostDays(days) => {
                         if let Some(date_joined) = self.account_created.clone() {
@@ -273,7 +414,7 @@ impl VrcMrdUser {
                                     .map(|dt| dt.naive_utc().date())
                         {
                             let account_age_days =
-                                (chrono::Local::now().naive_local().date() - joined_date).num_days();
+                                (chrono::Utc::now().date_naive() - joined_date).num_days();
                             // We add this template variable here because we have account_age_days here
                             templates
                                 .borrow_mut()
@@ -287,13 +428,13 @@ impl VrcMrdUser {
                             false
                         }
                     } else {
-                        advisories.iter().any(|a| a.id == advisory.id)
+                        kept_existing()
                     }
                 }
                 AdvisoryCondition::InstanceOwner(owner_id) => {
                     let instance_state = app.state::<crate::memory::instance::InstanceStateMutex>();
                     let instance_state = instance_state.lock();
-                    if let Some(owner) = instance_state.info.clone().unwrap().owner_id.flatten() {
+                    if let Some(owner) = instance_state.info.as_ref().and_then(|info| info.owner_id.clone().flatten()) {
                         if owner == owner_id {
                             return true;
                         }
@@ -307,6 +448,25 @@ impl VrcMrdUser {
                     }
                     false
                 }
+                AdvisoryCondition::AvatarMayBe { avatar_id, min_confidence } => {
+                    // Nothing to compare against until the avatar lookup finds something
+                    if self.avatar_id.is_none() && self.avatar_candidates.is_empty() {
+                        return kept_existing();
+                    }
+                    let confidence = self
+                        .avatar_candidates
+                        .iter()
+                        .filter(|c| c.avatar_id == avatar_id)
+                        .map(|c| c.confidence)
+                        .max()
+                        .or_else(|| (self.avatar_id.as_deref() == Some(avatar_id.as_str())).then_some(100));
+                    if let Some(confidence) = confidence.filter(|confidence| *confidence >= min_confidence) {
+                        templates.borrow_mut().insert("avatar_confidence", confidence.to_string());
+                        true
+                    } else {
+                        false
+                    }
+                }
                 AdvisoryCondition::AvatarNameContains(needle) => {
                     let avatar_name = self.avatar_name.clone();
                     let matches = avatar_name.clone().to_lowercase().contains(&needle.to_lowercase());
@@ -318,24 +478,32 @@ impl VrcMrdUser {
                 AdvisoryCondition::AvatarCreatorIs(ownerId) => {
                     let avatar_creator = self.avatar_creator.clone().unwrap_or_default();
                     if avatar_creator.is_empty() {
-                        return advisories.iter().any(|a| a.id == advisory.id);
+                        return kept_existing();
                     }
-                    let matches = avatar_creator.as_str() == ownerId.as_str();
-                    // if matches {
-                    //     templates.borrow_mut().insert("avatar_creator", avatar_creator.clone());
-                    // }
-                    matches
+                    avatar_creator.as_str() == ownerId.as_str()
                 },
+                AdvisoryCondition::AvatarIsPrivate | AdvisoryCondition::AvatarIsPublic => {
+                    let Some(release_status) = &self.avatar_release_status else {
+                        return kept_existing();
+                    };
+                    let wanted = if matches!(condition, AdvisoryCondition::AvatarIsPrivate) { "private" } else { "public" };
+                    release_status.eq_ignore_ascii_case(wanted)
+                }
+                AdvisoryCondition::AvatarImageFileIs(file_id) => self
+                    .avatar_images
+                    .iter()
+                    .filter_map(|image| get_file_id_from_image_url(image))
+                    .any(|id| id == file_id),
                 AdvisoryCondition::InGroupNameContains(needle) => {
-                    if self.groups.is_empty() && advisories.iter().any(|a| a.id == advisory.id) {
-                        // if the advisory is already active and groups are not available, assume the user is
-                        // still in the group.
-                        keep.borrow_mut().push(advisory.id.clone());
-                        return true;
+                    if !self.groups_known {
+                        // Without the group list, we can't tell; keep the advisory as it is
+                        return kept_existing();
                     }
                     let group = self.groups.iter().find(|g| g.name.to_lowercase().contains(&needle.to_lowercase()));
                     if let Some(group) = group {
+                        templates.borrow_mut().insert("group_id", group.id.clone());
                         templates.borrow_mut().insert("group_name", group.name.clone());
+                        templates.borrow_mut().insert("group_owner", group.owner_id.clone());
                         return true;
                     }
                     false
@@ -345,20 +513,35 @@ impl VrcMrdUser {
                         "Advisory condition not implemented in user advisory evaluation: {:?}",
                         condition
                     );
-                    advisories.iter().any(|a| a.id == advisory.id)
+                    kept_existing()
                     // If the condition is not implemented, keep the existing advisory if it's already present
                 }
-            }) {
+            };
+            let trace = (!advisory.is_expired()).then(|| {
+                advisory.condition.evaluate_traced(&|condition: AdvisoryCondition| {
+                    *kept.borrow_mut() = false;
+                    let value = self.traced_value(&condition);
+                    let matched = leaf(condition);
+                    ConditionResult {
+                        matched,
+                        value,
+                        kept_existing: *kept.borrow(),
+                    }
+                })
+            });
+            if let Some(trace) = trace.filter(|trace| trace.matched) {
                 if advisories.iter().any(|a| a.id == advisory.id) {
                     // Update the existing advisory, especially if the advisory settings changed
                     for existing in advisories.iter_mut() {
-                        if existing.id == advisory.id && !keep.borrow().contains(&advisory.id) {
+                        // Only re-render the message if the match didn't come from keeping the advisory
+                        if existing.id == advisory.id && !trace.kept_existing {
                             existing.message = apply_templating(
                                 advisory.message_template.clone().as_str(),
                                 &templates.borrow(),
                             );
                             existing.level = advisory.level.clone();
                             existing.relevant_group_id = relevant_group_id.borrow().clone();
+                            existing.trace = Some(trace.clone());
                         }
                     }
                 } else {
@@ -370,27 +553,46 @@ impl VrcMrdUser {
                         ),
                         level: advisory.level.clone(),
                         relevant_group_id: relevant_group_id.borrow().clone(),
+                        trace: Some(trace),
                     };
                     advisories.push(active_advisory.clone());
-                    publish_notice(
-                        app.clone(),
-                        make_notice(
-                            advisory,
-                            &active_advisory,
-                                &self.id,
-                                Some(match trigger {
-                                    AdvisoryTrigger::JoinLeave => format!("“{}” joined", self.username),
-                                    //AdvisoryTrigger::UserInfoUpdated => format!("User info updated for “{}”", self.username),
-                                    AdvisoryTrigger::AvatarSwitched => format!("“{}” changed to avatar “{}”", self.username, self.avatar_name),
-                                }),
-                        ),
-                    )
-                    .unwrap_or_else(|e| {
-                        eprintln!(
-                            "Failed to publish notice for advisory {}: {}",
-                            advisory.id, e
-                        );
-                    });
+                    // A bulk pass after the advisories changed may be silent, in which case the match
+                    // isn't counted and no actions run either. The caller may summarise it instead.
+                    if !matches!(trigger, AdvisoryTrigger::AdvisoriesChanged { notify: false }) {
+                        let mut title = match trigger {
+                            AdvisoryTrigger::JoinLeave => format!("“{}” joined", self.username),
+                            //AdvisoryTrigger::UserInfoUpdated => format!("User info updated for “{}”", self.username),
+                            AdvisoryTrigger::AvatarSwitched => format!("“{}” changed to avatar “{}”", self.username, self.avatar_name),
+                            AdvisoryTrigger::Reevaluated => format!("“{}” now matches", self.username),
+                            AdvisoryTrigger::AdvisoriesChanged { .. } => format!("“{}” matches a changed advisory", self.username),
+                        };
+                        // Avatar matches may be guesses, so say how sure we are
+                        if let Some(confidence) = templates.borrow().get("avatar_confidence") {
+                            title.push_str(&format!(" ({}% avatar match)", confidence));
+                        }
+                        let alerted = publish_notice(
+                            app.clone(),
+                            make_notice(
+                                advisory,
+                                &active_advisory,
+                                    &self.id,
+                                    Some(title),
+                            ),
+                            instance_id.clone(),
+                        )
+                        .unwrap_or_else(|e| {
+                            eprintln!(
+                                "Failed to publish notice for advisory {}: {}",
+                                advisory.id, e
+                            );
+                            true
+                        });
+                        // A repeat within the advisory's cooldown (i.e. a rejoin) isn't counted and doesn't run actions again
+                        if alerted {
+                            record_advisory_match(app.clone(), &advisory.id, &self.id, &trigger);
+                            run_advisory_actions(app.clone(), advisory, &self.id, templates.borrow().clone());
+                        }
+                    }
                 }
             } else {
                 // If the advisory condition is not met, remove it if it exists
@@ -406,4 +608,126 @@ impl VrcMrdUser {
         advisories
         // TODO: emit notices for each advisory added
     }
-}
\ No newline at end of file
+
+    /// Template variables that don't depend on the matching condition.
+    /// See [crate::advisories::templating::KNOWN_TEMPLATE_VARIABLES].
+    fn template_variables(&self, app: &AppHandle) -> HashMap<&'static str, String> {
+        let mut variables = HashMap::new();
+        variables.insert("username", self.username.clone());
+        variables.insert("user_id", self.id.clone());
+        variables.insert("join_time", self.join_time.to_string());
+        if let Some(trust_rank) = &self.trust_rank {
+            variables.insert("trust_rank", trust_rank.to_string());
+        }
+        if let Some(platform) = &self.platform {
+            variables.insert("platform", platform.clone());
+        }
+        if let Some(pronouns) = self.pronouns.clone().filter(|p| !p.is_empty()) {
+            variables.insert("pronouns", pronouns);
+        }
+        if let Some(status) = self.status.clone().filter(|s| !s.is_empty()) {
+            variables.insert("status", status);
+        }
+        if let Some(perf_rank) = &self.perf_rank {
+            variables.insert("perf_rank", perf_rank.to_string());
+        }
+        if !self.avatar_name.is_empty() {
+            variables.insert("avatar_name", self.avatar_name.clone());
+        }
+        if let Some(avatar_creator) = self.avatar_creator.clone().filter(|c| !c.is_empty()) {
+            variables.insert("avatar_creator", avatar_creator);
+        }
+        if let Some(joined_date) = self
+            .account_created
+            .and_then(|date_joined| chrono::DateTime::from_timestamp(date_joined, 0))
+            .map(|dt| dt.naive_utc().date())
+        {
+            let account_age_days = (chrono::Utc::now().date_naive() - joined_date).num_days();
+            variables.insert("account_age_days", account_age_days.to_string());
+        }
+        if self.groups_known {
+            variables.insert("group_count", self.groups.len().to_string());
+        }
+        let instance_name = {
+            let instance_state = app.state::<crate::memory::instance::InstanceStateMutex>();
+            let instance_state = instance_state.lock();
+            instance_state.info.as_ref().map(|info| {
+                info.display_name
+                    .clone()
+                    .flatten()
+                    .filter(|name| !name.is_empty())
+                    .unwrap_or_else(|| info.world.name.clone())
+            })
+        };
+        if let Some(instance_name) = instance_name {
+            variables.insert("instance_name", instance_name);
+        }
+        variables
+    }
+
+    /// The user's value a leaf condition compares against, for the evaluation trace.
+    /// `None` if the condition doesn't compare a user field, or the value isn't known.
+    fn traced_value(&self, condition: &AdvisoryCondition) -> Option<String> {
+        let join = |values: &[String]| (!values.is_empty()).then(|| values.join(", "));
+        match condition {
+            AdvisoryCondition::Is(_) | AdvisoryCondition::IsUntil { .. } => Some(self.id.clone()),
+            AdvisoryCondition::UsernameContains(_) => Some(self.username.clone()),
+            AdvisoryCondition::PronounContains(_) => self.pronouns.clone(),
+            AdvisoryCondition::StatusContains(_) => self.status.clone(),
+            AdvisoryCondition::BioContains(_) | AdvisoryCondition::BioMatches(_) => self.bio.clone(),
+            AdvisoryCondition::BioLinkDomainIs(_) => join(&self.bio_links),
+            AdvisoryCondition::AgeNotVerified => Some(self.age_verified.to_string()),
+            AdvisoryCondition::IsFriend => Some(self.is_friend.to_string()),
+            AdvisoryCondition::TrustRankAtMost(_) => self.trust_rank.as_ref().map(|rank| rank.to_string()),
+            AdvisoryCondition::HasTag(_) | AdvisoryCondition::TagPrefix(_) => join(&self.tags),
+            AdvisoryCondition::PlatformIs(_) => self.platform.clone(),
+            AdvisoryCondition::IsGroupMember(_)
+            | AdvisoryCondition::GroupCondition(_)
+            | AdvisoryCondition::InGroupNameContains(_) => {
+                join(&self.groups.iter().map(|g| g.name.clone()).collect::<Vec<_>>())
+            }
+            AdvisoryCondition::GroupCountAtLeast { .. } => self.groups_known.then(|| self.groups.len().to_string()),
+            AdvisoryCondition::TimeInInstanceAtLeastMinutes(_) => self
+                .is_in_instance()
+                .then(|| ((chrono::Utc::now().timestamp() - self.join_time) / 60).to_string()),
+            AdvisoryCondition::AccountAgeAtMostDays(_) => self
+                .account_created
+                .and_then(|date_joined| chrono::DateTime::from_timestamp(date_joined, 0))
+                .map(|dt| (chrono::Utc::now().date_naive() - dt.naive_utc().date()).num_days().to_string()),
+            AdvisoryCondition::AvatarMayBe { .. } => self.avatar_id.clone().or_else(|| {
+                join(&self.avatar_candidates.iter().map(|c| format!("{} ({}%)", c.avatar_id, c.confidence)).collect::<Vec<_>>())
+            }),
+            AdvisoryCondition::AvatarNameContains(_) => Some(self.avatar_name.clone()),
+            AdvisoryCondition::AvatarCreatorIs(_) => self.avatar_creator.clone(),
+            AdvisoryCondition::AvatarIsPrivate | AdvisoryCondition::AvatarIsPublic => self.avatar_release_status.clone(),
+            AdvisoryCondition::AvatarImageFileIs(_) => join(
+                &self
+                    .avatar_images
+                    .iter()
+                    .filter_map(|image| get_file_id_from_image_url(image))
+                    .collect::<Vec<_>>(),
+            ),
+            _ => None,
+        }
+    }
+}
+/// Evaluate a group condition against one of the user's groups.
+/// `None` if it can't be decided, i.e. it depends on the group's details and they weren't fetched.
+fn group_matches(group: &PartialGroup, condition: &AdvisoryGroupCondition) -> Option<bool> {
+    condition.evaluate_partial(&|condition: AdvisoryGroupCondition| condition.matches_group(group))
+}
+
+/// Get the lowercase host of a bio link, i.e. `discord.gg` for `https://discord.gg/abc`.
+/// Bio links don't always have a scheme, so this doesn't require one.
+fn link_domain(link: &str) -> Option<String> {
+    let link = link.trim();
+    let without_scheme = link.split_once("://").map_or(link, |(_, rest)| rest);
+    let authority = without_scheme.split(['/', '?', '#']).next()?;
+    let host = authority.rsplit_once('@').map_or(authority, |(_, host)| host);
+    let host = host.split(':').next()?.trim_start_matches("www.").to_lowercase();
+    if host.is_empty() {
+        None
+    } else {
+        Some(host)
+    }
+}
@@ -8,7 +8,9 @@ use crate::{api::xsoverlay::XSO_CONNECTED, notices::get_aumid};
 mod advisories;
 mod api;
 mod memory;
+mod migrations;
 mod monitoring;
+mod notice_history;
 mod notices;
 mod settings;
 mod types;
@@ -34,6 +36,10 @@ pub fn run() {
         .plugin(memory::instance::instance_memory_plugin())
         .plugin(memory::users::avatar::avatar_memory_plugin())
         .plugin(memory::advisories::advisory_memory_plugin())
+        .plugin(memory::moderation::moderation_memory_plugin())
+        .plugin(memory::group_roles::group_roles_memory_plugin())
+        .plugin(memory::group_details::group_details_memory_plugin())
+        .plugin(notice_history::notice_history_plugin())
         .plugin(api::vrchat_api_plugin())
         .invoke_handler(tauri::generate_handler![
             greet,
@@ -61,7 +67,25 @@ pub fn run() {
             advisories::update_advisory,
             advisories::remove_advisory,
             advisories::get_known_advisory_tags,
-            notices::get_all_notices,
+            advisories::get_quarantined_advisories,
+            advisories::stats::get_advisory_stats,
+            advisories::watchlists::get_watchlists,
+            advisories::templating::get_template_variables,
+            advisories::escalation::get_escalation_rules,
+            advisories::escalation::set_escalation_rules,
+            advisories::trusted_users::get_staff_trusted_users,
+            advisories::trusted_users::set_staff_trusted_users,
+            advisories::trusted_users::import_staff_trusted_users,
+            advisories::profiles::get_advisory_profiles,
+            advisories::profiles::get_active_advisory_profile,
+            advisories::profiles::set_advisory_profiles,
+            notices::get_notices,
+            notices::get_notices_since,
+            notices::acknowledge_notice,
+            notices::claim_notice,
+            notices::resolve_notice,
+            notices::reopen_notice,
+            notice_history::query_notice_history,
         ])
         .setup(|app| {
             #[cfg(target_os = "windows")]
@@ -114,6 +138,9 @@ pub fn run() {
             //keyring_core::set_default_store(dbus_secret_service_keyring_store::Store::new().unwrap());
             //app.handle().plugin(tauri_plugin_stronghold::Builder::with_argon2(&salt_path).build())?;
 
+            advisories::expiry::start_expiry_sweep(app.handle().clone());
+            advisories::reevaluation::start_reevaluation(app.handle().clone());
+
             // XSOverlay WebSocket connection setup
             let appclone = app.handle().clone();
             let stophandle = tauri::async_runtime::spawn(async move {
@@ -1,39 +1,125 @@
-use std::ops::DerefMut;
+use std::{collections::{HashMap, HashSet}, ops::DerefMut};
 
+use nid::{alphabet::Base58Alphabet, Nanoid};
 use parking_lot::Mutex;
 
 use tauri::{Manager, Runtime};
 
 use crate::{
-    advisories::ADVISORIES_CONFIG_KEY,
-    settings::get_config,
-    types::advisories::{Advisory, AdvisoryCondition, Notice},
+    advisories::{
+        escalation::load_escalation_rules,
+        stats::{load_advisory_stats, merge_advisory_stats, schedule_stats_save},
+        profiles::{load_advisory_profiles, switch_advisory_profile},
+        trusted_users::load_staff_trusted_users,
+        watchlists::{add_to_watchlist, load_watchlists, save_watchlists},
+        ADVISORIES_PAYLOAD,
+    },
+    types::advisories::{Advisory, AdvisoryProfile, AdvisoryStats, DataRequirements, EscalationRule, Notice},
 };
 
 pub struct AdvisoryMemory {
-    pub has_group_membership_advisory: bool,
+    /// The data the active advisories need, so only that is fetched for each user.
+    pub data_requirements: DataRequirements,
     pub active_advisories: Vec<Advisory>,
     pub all_advisories: Vec<Advisory>,
     pub notices: Vec<Notice>,
+    /// The last notice ID or revision handed out. See [Notice::id].
+    pub notice_sequence: u64,
+    /// [Self::notice_sequence] when the notice list was last cleared, so clients can tell their copy is stale.
+    pub notices_cleared_at: u64,
+    /// A random ID for this run of the app. Notice cursors are only comparable within a session,
+    /// since revisions aren't kept across restarts.
+    pub notice_session: String,
+    /// Whether the advisories have been loaded from the config store yet.
+    pub loaded: bool,
+    /// Whether the other payloads have been loaded from the config store yet. Each is loaded (and can fail) on its own,
+    /// and isn't saved until it has loaded, so a change made during startup can't overwrite what's stored.
+    pub stats_loaded: bool,
+    pub watchlists_loaded: bool,
+    pub escalation_rules_loaded: bool,
+    pub staff_trusted_users_loaded: bool,
+    pub profiles_loaded: bool,
+    /// Hit statistics, keyed by advisory ID.
+    pub stats: HashMap<String, AdvisoryStats>,
+    /// When each (advisory ID, user ID) pair last alerted, as a Unix timestamp.
+    /// Used to apply [Advisory::cooldown_minutes]. Kept across instance changes.
+    pub last_notified: HashMap<(String, String), i64>,
+    /// (advisory ID, user ID) pairs whose notice was handled while [crate::notices::ACKNOWLEDGE_CANCELS_ALERTS_CONFIG_KEY]
+    /// was on. Repeats are collapsed into that notice without alerting, cooldown or not. Cleared with the notice list.
+    pub silenced_notices: HashSet<(String, String)>,
+    /// Named lists of user IDs, keyed by name.
+    pub watchlists: HashMap<String, Vec<String>>,
+    pub escalation_rules: Vec<EscalationRule>,
+    /// User IDs that the host's moderators trust. See [crate::advisories::trusted_users].
+    pub staff_trusted_user_ids: Vec<String>,
+    /// See [crate::advisories::profiles].
+    pub profiles: Vec<AdvisoryProfile>,
+    /// The profile for the current instance, if any. Limits [Self::active_advisories].
+    pub active_profile_id: Option<String>,
 }
 
 impl AdvisoryMemory {
     pub fn new() -> Self {
         Self {
-            has_group_membership_advisory: false,
+            data_requirements: DataRequirements::default(),
             active_advisories: Vec::new(),
             all_advisories: Vec::new(),
             notices: Vec::new(),
+            notice_sequence: 0,
+            notices_cleared_at: 0,
+            notice_session: Nanoid::<12, Base58Alphabet>::new().to_string(),
+            loaded: false,
+            stats_loaded: false,
+            watchlists_loaded: false,
+            escalation_rules_loaded: false,
+            staff_trusted_users_loaded: false,
+            profiles_loaded: false,
+            stats: HashMap::new(),
+            last_notified: HashMap::new(),
+            silenced_notices: HashSet::new(),
+            watchlists: HashMap::new(),
+            escalation_rules: Vec::new(),
+            staff_trusted_user_ids: Vec::new(),
+            profiles: Vec::new(),
+            active_profile_id: None,
         }
     }
     /// Set the advisories in the struct. Automatically updates active_advisories as well.
     pub fn set(&mut self, advisories: Vec<Advisory>) {
-        self.active_advisories = advisories.iter().filter(|a| a.active).cloned().collect();
-        self.all_advisories = advisories.clone();
-        self.has_group_membership_advisory = self
-            .active_advisories
+        self.all_advisories = advisories;
+        self.refresh_active_advisories();
+    }
+    /// The next notice ID or revision.
+    pub fn next_notice_sequence(&mut self) -> u64 {
+        self.notice_sequence += 1;
+        self.notice_sequence
+    }
+    /// Clear the notice list, i.e. when changing instances. The notices stay in History.
+    pub fn clear_notices(&mut self) {
+        self.notices.clear();
+        self.silenced_notices.clear();
+        // Take a new sequence number, so a client whose cursor was up to date before the clear still sees it as stale
+        self.notices_cleared_at = self.next_notice_sequence();
+    }
+    /// The active profile, if it still exists and is enabled.
+    pub fn active_profile(&self) -> Option<&AdvisoryProfile> {
+        let id = self.active_profile_id.as_ref()?;
+        self.profiles.iter().find(|p| &p.id == id && p.active)
+    }
+    /// Recompute active_advisories from all_advisories and the active profile.
+    pub fn refresh_active_advisories(&mut self) {
+        let profile = self.active_profile().cloned();
+        self.active_advisories = self
+            .all_advisories
             .iter()
-            .any(|a| matches!(a.condition, AdvisoryCondition::IsGroupMember(_)));
+            .filter(|a| a.active && profile.as_ref().is_none_or(|p| p.includes(a)))
+            .cloned()
+            .collect();
+        let mut data_requirements = DataRequirements::default();
+        for advisory in self.active_advisories.iter() {
+            data_requirements.merge(advisory.condition.data_requirements());
+        }
+        self.data_requirements = data_requirements;
     }
 }
 
@@ -44,23 +130,90 @@ pub fn advisory_memory_plugin<R: Runtime>() -> tauri::plugin::TauriPlugin<R> {
             app.manage(advisories);
             let app_clone = app.clone();
             tauri::async_runtime::spawn(async move {
-                // Load advisories from config on startup
-                let adv = match get_config(app_clone.clone(), ADVISORIES_CONFIG_KEY.to_string())
-                    .await
-                    .unwrap()
-                {
-                    Some(existing) => existing,
-                    None => "[]".to_string(),
-                };
-                let adv: Vec<Advisory> = serde_json::from_str(&adv)
-                    .map_err(|e| e.to_string())
-                    .unwrap();
-                {
-                    app_clone
-                        .state::<Mutex<AdvisoryMemory>>()
-                        .lock()
-                        .deref_mut()
-                        .set(adv);
+                // Load advisories from config on startup, migrating them if they were saved by an older version.
+                // A payload that fails to load keeps its flag unset, so nothing overwrites what's stored.
+                match ADVISORIES_PAYLOAD.load_records(app_clone.clone()).await {
+                    Ok(adv) => {
+                        let advisory_memory = app_clone.state::<Mutex<AdvisoryMemory>>();
+                        let mut advisory_memory = advisory_memory.lock();
+                        advisory_memory.deref_mut().set(adv);
+                        advisory_memory.loaded = true;
+                    }
+                    Err(e) => eprintln!("Failed to load advisories: {}", e),
+                }
+                match load_advisory_stats(app_clone.clone()).await {
+                    Ok(mut stats) => {
+                        let recorded = {
+                            let advisory_memory = app_clone.state::<Mutex<AdvisoryMemory>>();
+                            let mut advisory_memory = advisory_memory.lock();
+                            // Keep the matches recorded while loading
+                            let recorded = std::mem::take(&mut advisory_memory.stats);
+                            let any_recorded = !recorded.is_empty();
+                            merge_advisory_stats(&mut stats, recorded);
+                            advisory_memory.stats = stats;
+                            advisory_memory.stats_loaded = true;
+                            any_recorded
+                        };
+                        if recorded {
+                            schedule_stats_save(app_clone.clone());
+                        }
+                    }
+                    Err(e) => eprintln!("Failed to load advisory stats: {}", e),
+                }
+                match load_watchlists(app_clone.clone()).await {
+                    Ok(mut watchlists) => {
+                        let added = {
+                            let advisory_memory = app_clone.state::<Mutex<AdvisoryMemory>>();
+                            let mut advisory_memory = advisory_memory.lock();
+                            // Keep the users added (i.e. by advisory actions) while loading
+                            let mut added = false;
+                            for (name, user_ids) in std::mem::take(&mut advisory_memory.watchlists) {
+                                for user_id in user_ids {
+                                    added |= add_to_watchlist(&mut watchlists, &name, &user_id);
+                                }
+                            }
+                            advisory_memory.watchlists = watchlists;
+                            advisory_memory.watchlists_loaded = true;
+                            added
+                        };
+                        if added {
+                            if let Err(e) = save_watchlists(app_clone.clone()).await {
+                                eprintln!("Failed to save watchlists: {}", e);
+                            }
+                        }
+                    }
+                    Err(e) => eprintln!("Failed to load watchlists: {}", e),
+                }
+                match load_escalation_rules(app_clone.clone()).await {
+                    Ok(rules) => {
+                        let advisory_memory = app_clone.state::<Mutex<AdvisoryMemory>>();
+                        let mut advisory_memory = advisory_memory.lock();
+                        advisory_memory.escalation_rules = rules;
+                        advisory_memory.escalation_rules_loaded = true;
+                    }
+                    Err(e) => eprintln!("Failed to load escalation rules: {}", e),
+                }
+                match load_staff_trusted_users(app_clone.clone()).await {
+                    Ok(user_ids) => {
+                        let advisory_memory = app_clone.state::<Mutex<AdvisoryMemory>>();
+                        let mut advisory_memory = advisory_memory.lock();
+                        advisory_memory.staff_trusted_user_ids = user_ids;
+                        advisory_memory.staff_trusted_users_loaded = true;
+                    }
+                    Err(e) => eprintln!("Failed to load moderator-trusted users: {}", e),
+                }
+                match load_advisory_profiles(app_clone.clone()).await {
+                    Ok(profiles) => {
+                        {
+                            let advisory_memory = app_clone.state::<Mutex<AdvisoryMemory>>();
+                            let mut advisory_memory = advisory_memory.lock();
+                            advisory_memory.profiles = profiles;
+                            advisory_memory.profiles_loaded = true;
+                        }
+                        // We may already be in an instance
+                        switch_advisory_profile(app_clone.clone());
+                    }
+                    Err(e) => eprintln!("Failed to load advisory profiles: {}", e),
                 }
             });
             Ok(())
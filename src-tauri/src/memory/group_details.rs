@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+
+use parking_lot::Mutex;
+use tauri::{AppHandle, Listener, Manager, Runtime};
+
+use crate::{
+    memory::advisories::AdvisoryMemory,
+    try_request,
+    types::{advisories_groups::AdvisoryGroupCondition, GroupDetails, PartialGroup},
+};
+
+/// Details of groups, keyed by group ID. Groups are shared between many users, so this saves
+/// fetching the same group again for every user in it. Cleared on a cache refresh.
+#[derive(Default)]
+pub struct GroupDetailsCache {
+    pub inner: HashMap<String, GroupDetails>,
+}
+
+pub fn group_details_memory_plugin<R: Runtime>() -> tauri::plugin::TauriPlugin<R> {
+    tauri::plugin::Builder::new("group_details_memory")
+        .setup(|app, _api| {
+            app.manage(Mutex::new(GroupDetailsCache::default()));
+            let app_clone = app.app_handle().clone();
+            app.listen("vrcmrd:cache_refresh", move |_| {
+                let cache = app_clone.state::<Mutex<GroupDetailsCache>>();
+                let mut cache = cache.lock();
+                cache.inner.clear();
+            });
+            Ok(())
+        })
+        .build()
+}
+
+/// Attach details to the user's groups, fetching them only for groups where they could change whether
+/// an active advisory matches. Groups already in the cache always get their details attached.
+pub async fn attach_group_details(app: AppHandle, groups: &mut [PartialGroup]) {
+    let conditions: Vec<AdvisoryGroupCondition> = {
+        let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+        let advisory_memory = advisory_memory.lock();
+        advisory_memory
+            .active_advisories
+            .iter()
+            .flat_map(|a| a.condition.group_conditions())
+            .filter(|c| c.needs_details())
+            .cloned()
+            .collect()
+    };
+    for group in groups.iter_mut() {
+        let cached = {
+            let cache = app.state::<Mutex<GroupDetailsCache>>();
+            let cache = cache.lock();
+            cache.inner.get(&group.id).cloned()
+        };
+        if cached.is_some() {
+            group.details = cached;
+            continue;
+        }
+        // Only fetch if the result depends on the details, i.e. not for groups whose name already rules them out
+        let needed = conditions
+            .iter()
+            .any(|condition| condition.evaluate_partial(&|c: AdvisoryGroupCondition| c.matches_group(group)).is_none());
+        if !needed {
+            continue;
+        }
+        if let Some(details) = fetch_group_details(app.clone(), &group.id).await {
+            let cache = app.state::<Mutex<GroupDetailsCache>>();
+            let mut cache = cache.lock();
+            cache.inner.insert(group.id.clone(), details.clone());
+            group.details = Some(details);
+        }
+    }
+}
+
+async fn fetch_group_details(app: AppHandle, group_id: &str) -> Option<GroupDetails> {
+    println!("Fetching group details for group ID: {}", group_id);
+    let group = try_request!(app.clone(), |config| {
+        vrchatapi::apis::groups_api::get_group(config, group_id, None)
+    }, { wait_for_api_ready: true })
+    .await;
+    match group {
+        Ok(Some(group)) => Some(GroupDetails {
+            created_at: group
+                .created_at
+                .and_then(|created_at| chrono::DateTime::parse_from_rfc3339(&created_at).ok())
+                .map(|created_at| created_at.timestamp()),
+            join_state: group.join_state.map(|join_state| join_state.to_string()),
+            is_verified: group.is_verified,
+        }),
+        Ok(None) => None,
+        Err(e) => {
+            eprintln!("Failed to fetch group details for group ID {}: {:?}", group_id, e);
+            None
+        }
+    }
+}
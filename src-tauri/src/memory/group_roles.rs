@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+
+use parking_lot::Mutex;
+use tauri::{AppHandle, Listener, Manager, Runtime};
+
+use crate::{memory::instance::InstanceStateMutex, try_request};
+
+/// Role IDs of members of the group hosting the current instance, so group staff can be recognised.
+/// Only the hosting group is cached; the cache is cleared when the hosting group changes or on a cache refresh.
+#[derive(Default)]
+pub struct GroupRoles {
+    /// The group the cached roles belong to.
+    pub group_id: Option<String>,
+    /// The IDs of the group's management (staff) roles, or `None` if they haven't been fetched yet.
+    pub management_role_ids: Option<Vec<String>>,
+    /// Role IDs per user ID. Users who aren't members of the group have an empty list.
+    pub member_role_ids: HashMap<String, Vec<String>>,
+}
+
+impl GroupRoles {
+    /// Clear the cache if it's for a different group.
+    fn for_group(&mut self, group_id: &str) -> &mut Self {
+        if self.group_id.as_deref() != Some(group_id) {
+            *self = GroupRoles {
+                group_id: Some(group_id.to_string()),
+                ..Default::default()
+            };
+        }
+        self
+    }
+    /// The user's role IDs in the group, or `None` if they aren't known.
+    /// An empty group ID means the group hosting the instance.
+    pub fn role_ids(&self, group_id: &str, user_id: &str) -> Option<&Vec<String>> {
+        if !group_id.is_empty() && self.group_id.as_deref() != Some(group_id) {
+            return None;
+        }
+        self.member_role_ids.get(user_id)
+    }
+    /// Whether the user holds one of the hosting group's management roles,
+    /// or `None` if the group's roles or the user's roles aren't known.
+    pub fn is_staff(&self, user_id: &str) -> Option<bool> {
+        let management_role_ids = self.management_role_ids.as_ref()?;
+        let role_ids = self.member_role_ids.get(user_id)?;
+        Some(role_ids.iter().any(|id| management_role_ids.contains(id)))
+    }
+}
+
+pub fn group_roles_memory_plugin<R: Runtime>() -> tauri::plugin::TauriPlugin<R> {
+    tauri::plugin::Builder::new("group_roles_memory")
+        .setup(|app, _api| {
+            app.manage(Mutex::new(GroupRoles::default()));
+            let app_clone = app.app_handle().clone();
+            app.listen("vrcmrd:cache_refresh", move |_| {
+                let group_roles = app_clone.state::<Mutex<GroupRoles>>();
+                let mut group_roles = group_roles.lock();
+                *group_roles = GroupRoles::default();
+            });
+            Ok(())
+        })
+        .build()
+}
+
+/// The ID of the group hosting the current instance (`grp_***`), if it's a group instance.
+pub fn hosting_group_id<R: Runtime>(app: &AppHandle<R>) -> Option<String> {
+    let instance_state = app.state::<InstanceStateMutex>();
+    let instance_state = instance_state.lock();
+    instance_state
+        .id_info
+        .as_ref()
+        .and_then(|id_info| id_info.owner.clone())
+        .filter(|owner| owner.starts_with("grp_"))
+}
+
+/// Fetch the user's roles in the group hosting the current instance, unless they're already cached.
+/// Outside of group instances, this clears the cache instead.
+pub async fn fetch_hosting_group_roles(app: AppHandle, user_id: &str) {
+    let Some(group_id) = hosting_group_id(&app) else {
+        // Roles from a previous group's instance no longer apply
+        let group_roles = app.state::<Mutex<GroupRoles>>();
+        let mut group_roles = group_roles.lock();
+        *group_roles = GroupRoles::default();
+        return;
+    };
+    let (needs_roles, needs_member) = {
+        let group_roles = app.state::<Mutex<GroupRoles>>();
+        let mut group_roles = group_roles.lock();
+        let group_roles = group_roles.for_group(&group_id);
+        (
+            group_roles.management_role_ids.is_none(),
+            !group_roles.member_role_ids.contains_key(user_id),
+        )
+    };
+    if needs_roles {
+        println!("Fetching roles for group ID: {}", group_id);
+        let roles = try_request!(app.clone(), |config| {
+            vrchatapi::apis::groups_api::get_group_roles(config, &group_id)
+        }, { wait_for_api_ready: true })
+        .await;
+        match roles {
+            Ok(Some(roles)) => {
+                let management_role_ids = roles
+                    .into_iter()
+                    .filter(|role| role.is_management_role.unwrap_or(false))
+                    .filter_map(|role| role.id)
+                    .collect();
+                let group_roles = app.state::<Mutex<GroupRoles>>();
+                let mut group_roles = group_roles.lock();
+                group_roles.for_group(&group_id).management_role_ids = Some(management_role_ids);
+            }
+            Ok(None) => {}
+            Err(e) => eprintln!("Failed to fetch roles for group ID {}: {:?}", group_id, e),
+        }
+    }
+    if needs_member {
+        println!("Fetching group member roles for user ID {} in group ID {}", user_id, group_id);
+        let member = try_request!(app.clone(), |config| {
+            vrchatapi::apis::groups_api::get_group_member(config, &group_id, user_id)
+        }, { wait_for_api_ready: true })
+        .await;
+        let role_ids = match member {
+            Ok(Some(member)) => {
+                let mut role_ids = member.role_ids.unwrap_or_default();
+                for role_id in member.m_role_ids.unwrap_or_default() {
+                    if !role_ids.contains(&role_id) {
+                        role_ids.push(role_id);
+                    }
+                }
+                role_ids
+            }
+            // Not a member of the group
+            Err(vrchatapi::apis::Error::ResponseError(response)) if response.status == reqwest::StatusCode::NOT_FOUND => Vec::new(),
+            Ok(None) => return,
+            Err(e) => {
+                eprintln!("Failed to fetch group member roles for user ID {}: {:?}", user_id, e);
+                return;
+            }
+        };
+        let group_roles = app.state::<Mutex<GroupRoles>>();
+        let mut group_roles = group_roles.lock();
+        group_roles
+            .for_group(&group_id)
+            .member_role_ids
+            .insert(user_id.to_string(), role_ids);
+    }
+}
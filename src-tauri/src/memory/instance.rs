@@ -224,3 +224,10 @@ pub async fn get_instance_info<R: Runtime>(
         Ok(None)
     }
 }
+
+/// The ID of the instance we're in, if any.
+pub fn current_instance_id<R: Runtime>(app: &tauri::AppHandle<R>) -> Option<String> {
+    let instance_state = app.state::<InstanceStateMutex>();
+    let instance_state = instance_state.lock();
+    instance_state.id.clone()
+}
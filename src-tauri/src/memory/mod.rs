@@ -1,3 +1,6 @@
 pub mod advisories;
+pub mod group_details;
+pub mod group_roles;
 pub mod instance;
+pub mod moderation;
 pub mod users;
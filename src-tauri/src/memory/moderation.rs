@@ -0,0 +1,134 @@
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tauri::{Manager, Runtime};
+
+use crate::{migrations::VersionedPayload, settings::get_config};
+
+/// Kick and ban events, kept per user ID so they survive the user leaving and rejoining.
+/// This payload was introduced after schema versioning, so it starts at version 0.
+pub const MODERATION_HISTORY_PAYLOAD: VersionedPayload = VersionedPayload {
+    key: "moderation_history",
+    current_version: 0,
+    migrations: &[],
+};
+
+/// How many hours to keep kick and ban events for.
+pub const MODERATION_HISTORY_HOURS_CONFIG_KEY: &str = "moderation_history_hours";
+pub const DEFAULT_MODERATION_HISTORY_HOURS: u32 = 24;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ModerationKind {
+    Kick,
+    Ban,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModerationEvent {
+    pub user_id: String,
+    pub kind: ModerationKind,
+    /// When the event happened, as a Unix timestamp (from the log line).
+    pub at: i64,
+}
+
+pub struct ModerationHistory {
+    pub events: Vec<ModerationEvent>,
+    pub retention_hours: u32,
+}
+
+impl ModerationHistory {
+    /// Add an event. Returns false if it was already recorded, i.e. when the log is re-read on startup.
+    pub fn record(&mut self, event: ModerationEvent) -> bool {
+        if self.events.contains(&event) {
+            return false;
+        }
+        self.events.push(event);
+        true
+    }
+    /// Drop events older than the retention window.
+    pub fn prune(&mut self) {
+        let cutoff = chrono::Utc::now().timestamp() - self.retention_hours as i64 * 60 * 60;
+        self.events.retain(|e| e.at >= cutoff);
+    }
+    /// The most recent event of the given kind for the user within the given number of hours, if any.
+    pub fn latest_within_hours(&self, user_id: &str, kind: ModerationKind, hours: u32) -> Option<&ModerationEvent> {
+        let cutoff = chrono::Utc::now().timestamp() - hours as i64 * 60 * 60;
+        self.events
+            .iter()
+            .filter(|e| e.user_id == user_id && e.kind == kind && e.at >= cutoff)
+            .max_by_key(|e| e.at)
+    }
+    /// Whether the user was kicked or banned within the retention window.
+    pub fn recently_moderated(&self, user_id: &str) -> bool {
+        let cutoff = chrono::Utc::now().timestamp() - self.retention_hours as i64 * 60 * 60;
+        self.events.iter().any(|e| e.user_id == user_id && e.at >= cutoff)
+    }
+}
+
+pub fn moderation_memory_plugin<R: Runtime>() -> tauri::plugin::TauriPlugin<R> {
+    tauri::plugin::Builder::new("moderation_memory")
+        .setup(|app, _api| {
+            app.manage(Mutex::new(ModerationHistory {
+                events: Vec::new(),
+                retention_hours: DEFAULT_MODERATION_HISTORY_HOURS,
+            }));
+            let app_clone = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let retention_hours = retention_hours(app_clone.clone()).await;
+                let events: Vec<ModerationEvent> = MODERATION_HISTORY_PAYLOAD
+                    .load_records(app_clone.clone())
+                    .await
+                    .unwrap_or_else(|e| {
+                        eprintln!("Failed to load moderation history: {}", e);
+                        Vec::new()
+                    });
+                let history = app_clone.state::<Mutex<ModerationHistory>>();
+                let mut history = history.lock();
+                history.retention_hours = retention_hours;
+                // Keep anything recorded while loading
+                for event in events {
+                    history.record(event);
+                }
+                history.prune();
+            });
+            Ok(())
+        })
+        .build()
+}
+
+async fn retention_hours<R: Runtime>(app: tauri::AppHandle<R>) -> u32 {
+    get_config(app, MODERATION_HISTORY_HOURS_CONFIG_KEY.to_string())
+        .await
+        .ok()
+        .flatten()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_MODERATION_HISTORY_HOURS)
+}
+
+/// Record a kick or ban, prune old events and save the history.
+pub fn record_moderation_event<R: Runtime>(app: tauri::AppHandle<R>, event: ModerationEvent) {
+    {
+        let history = app.state::<Mutex<ModerationHistory>>();
+        let mut history = history.lock();
+        if !history.record(event) {
+            return;
+        }
+    }
+    tauri::async_runtime::spawn(async move {
+        let retention_hours = retention_hours(app.clone()).await;
+        let events = {
+            let history = app.state::<Mutex<ModerationHistory>>();
+            let mut history = history.lock();
+            history.retention_hours = retention_hours;
+            history.prune();
+            history.events.clone()
+        };
+        let result = match serde_json::to_value(&events) {
+            Ok(value) => MODERATION_HISTORY_PAYLOAD.save(app, &value).await,
+            Err(e) => Err(e.to_string()),
+        };
+        if let Err(e) = result {
+            eprintln!("Failed to save moderation history: {}", e);
+        }
+    });
+}
@@ -76,7 +76,7 @@ pub async fn get_user_info(
                         existing_user.update_from(
                             app.clone(),
                             &Into::<CommonUser>::into(remote.clone()),
-                            Vec::new(),
+                            None,
                         );
                         local_user = Some(existing_user.clone());
                     }
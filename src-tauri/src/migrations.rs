@@ -0,0 +1,248 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::Runtime;
+
+use crate::settings::{get_config, remove_config, update_config};
+
+/// A migration takes a payload stored at one schema version and returns it in the next version's shape.
+pub type Migration = fn(Value) -> Result<Value, String>;
+
+/// A JSON payload stored in the config store, along with the schema version it was written with.
+///
+/// The payload is stored under `key`, and the schema version under `{key}_schema_version`.
+/// Payloads written before versioning was introduced have no version key and are treated as version 0.
+/// Plain settings (i.e. `tts_preference`) are single strings that each caller parses with a default, so they aren't versioned.
+pub struct VersionedPayload {
+    pub key: &'static str,
+    /// The schema version that the current code reads and writes.
+    pub current_version: u32,
+    /// Migrations between versions, in order. `migrations[n]` migrates a payload from version `n` to `n + 1`,
+    /// so this should always have `current_version` entries.
+    pub migrations: &'static [Migration],
+}
+
+/// A record that could not be loaded, kept aside so it can be inspected (or fixed by hand) later.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuarantinedRecord {
+    /// The raw record (or the whole payload, if it couldn't be parsed at all).
+    pub record: Value,
+    pub error: String,
+    /// The schema version the record was stored with.
+    pub schema_version: u32,
+    /// Timestamp of when the record was quarantined, in RFC 3339 format.
+    pub quarantined_at: String,
+}
+
+impl VersionedPayload {
+    pub fn version_key(&self) -> String {
+        format!("{}_schema_version", self.key)
+    }
+    pub fn backup_key(&self, version: u32) -> String {
+        format!("{}_backup_v{}", self.key, version)
+    }
+    pub fn quarantine_key(&self) -> String {
+        format!("{}_quarantine", self.key)
+    }
+
+    /// Load the payload and migrate it to the current schema version.
+    ///
+    /// Before migrating, the old payload is backed up under `{key}_backup_v{version}`.
+    /// If the payload can't be parsed or migrated, it is quarantined and `None` is returned,
+    /// so the caller can start fresh instead of crashing. The quarantined payload is also reset (see [VersionedPayload::reset]),
+    /// so it isn't quarantined again on every load.
+    /// The migrated payload is not written back; the caller should do that with [VersionedPayload::save]
+    /// once it has dropped any records it couldn't load.
+    pub async fn load<R: Runtime>(&self, app: tauri::AppHandle<R>) -> Result<Option<Value>, String> {
+        let raw = match get_config(app.clone(), self.key.to_string()).await? {
+            Some(raw) => raw,
+            None => return Ok(None),
+        };
+        let version = self.stored_version(app.clone()).await?;
+        let value: Value = match serde_json::from_str(&raw) {
+            Ok(value) => value,
+            Err(e) => {
+                eprintln!("Stored payload '{}' is not valid JSON, quarantining it: {}", self.key, e);
+                self.quarantine(app.clone(), vec![QuarantinedRecord {
+                    record: Value::String(raw),
+                    error: e.to_string(),
+                    schema_version: version,
+                    quarantined_at: chrono::Utc::now().to_rfc3339(),
+                }]).await?;
+                self.reset(app.clone()).await?;
+                return Ok(None);
+            }
+        };
+        if version > self.current_version {
+            // Written by a newer version of VRCMRD. Keep a copy, since saving will downgrade it.
+            eprintln!(
+                "Stored payload '{}' has schema version {}, which is newer than the supported version {}. Loading it anyway.",
+                self.key, version, self.current_version
+            );
+            update_config(app.clone(), self.backup_key(version), raw).await?;
+            return Ok(Some(value));
+        }
+        if version == self.current_version {
+            return Ok(Some(value));
+        }
+        println!(
+            "Migrating stored payload '{}' from schema version {} to {}",
+            self.key, version, self.current_version
+        );
+        update_config(app.clone(), self.backup_key(version), raw.clone()).await?;
+        match self.migrate(value, version) {
+            Ok(value) => Ok(Some(value)),
+            Err(e) => {
+                eprintln!("Failed to migrate stored payload '{}', quarantining it: {}", self.key, e);
+                self.quarantine(app.clone(), vec![QuarantinedRecord {
+                    record: Value::String(raw),
+                    error: e,
+                    schema_version: version,
+                    quarantined_at: chrono::Utc::now().to_rfc3339(),
+                }]).await?;
+                self.reset(app.clone()).await?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Run the migrations from `version` up to the current schema version.
+    pub fn migrate(&self, mut value: Value, version: u32) -> Result<Value, String> {
+        for (from, migration) in self.migrations.iter().enumerate().skip(version as usize) {
+            value = migration(value).map_err(|e| format!("from schema version {}: {}", from, e))?;
+        }
+        Ok(value)
+    }
+
+    /// Load the payload as a list of records, migrating it first.
+    /// Records that don't deserialize are quarantined instead of failing the whole load.
+    /// If anything was migrated or quarantined, the cleaned-up list is saved back.
+    pub async fn load_records<R: Runtime, T>(&self, app: tauri::AppHandle<R>) -> Result<Vec<T>, String>
+    where
+        T: Serialize + for<'de> Deserialize<'de>,
+    {
+        let version = self.stored_version(app.clone()).await?;
+        let value = match self.load(app.clone()).await? {
+            Some(value) => value,
+            None => return Ok(Vec::new()),
+        };
+        let records = match value {
+            Value::Array(records) => records,
+            other => {
+                self.quarantine(app.clone(), vec![QuarantinedRecord {
+                    record: other,
+                    error: "Expected a list of records".to_string(),
+                    schema_version: version,
+                    quarantined_at: chrono::Utc::now().to_rfc3339(),
+                }]).await?;
+                self.reset(app.clone()).await?;
+                return Ok(Vec::new());
+            }
+        };
+        let mut loaded = Vec::with_capacity(records.len());
+        let mut quarantined = Vec::new();
+        for record in records {
+            match serde_json::from_value::<T>(record.clone()) {
+                Ok(record) => loaded.push(record),
+                Err(e) => {
+                    eprintln!("Failed to load a record from '{}', quarantining it: {}", self.key, e);
+                    quarantined.push(QuarantinedRecord {
+                        record,
+                        error: e.to_string(),
+                        schema_version: self.current_version,
+                        quarantined_at: chrono::Utc::now().to_rfc3339(),
+                    });
+                }
+            }
+        }
+        let any_quarantined = !quarantined.is_empty();
+        if any_quarantined {
+            self.quarantine(app.clone(), quarantined).await?;
+        }
+        if version != self.current_version || any_quarantined {
+            let value = serde_json::to_value(&loaded).map_err(|e| e.to_string())?;
+            self.save(app.clone(), &value).await?;
+        }
+        Ok(loaded)
+    }
+
+    /// Save the payload, tagged with the current schema version.
+    pub async fn save<R: Runtime>(&self, app: tauri::AppHandle<R>, value: &Value) -> Result<(), String> {
+        let payload = serde_json::to_string(value).map_err(|e| e.to_string())?;
+        update_config(app.clone(), self.key.to_string(), payload).await?;
+        update_config(app.clone(), self.version_key(), self.current_version.to_string()).await?;
+        Ok(())
+    }
+
+    /// Remove the stored payload and its version, so the next load starts fresh as if nothing was stored.
+    pub async fn reset<R: Runtime>(&self, app: tauri::AppHandle<R>) -> Result<(), String> {
+        remove_config(app.clone(), self.key.to_string()).await?;
+        remove_config(app, self.version_key()).await
+    }
+
+    /// Get the records that have been quarantined for this payload.
+    pub async fn quarantined<R: Runtime>(&self, app: tauri::AppHandle<R>) -> Result<Vec<QuarantinedRecord>, String> {
+        match get_config(app, self.quarantine_key()).await? {
+            Some(raw) => Ok(serde_json::from_str(&raw).unwrap_or_else(|e| {
+                eprintln!("Quarantine list for '{}' could not be read: {}", self.key, e);
+                Vec::new()
+            })),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    async fn quarantine<R: Runtime>(&self, app: tauri::AppHandle<R>, records: Vec<QuarantinedRecord>) -> Result<(), String> {
+        let mut existing = self.quarantined(app.clone()).await?;
+        existing.extend(records);
+        let payload = serde_json::to_string(&existing).map_err(|e| e.to_string())?;
+        update_config(app, self.quarantine_key(), payload).await
+    }
+
+    async fn stored_version<R: Runtime>(&self, app: tauri::AppHandle<R>) -> Result<u32, String> {
+        Ok(get_config(app, self.version_key())
+            .await?
+            .and_then(|v| v.parse::<u32>().ok())
+            .unwrap_or(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn add_field(mut value: Value) -> Result<Value, String> {
+        value.as_object_mut().ok_or("Expected an object")?.insert("added".to_string(), Value::Bool(true));
+        Ok(value)
+    }
+
+    fn double_count(mut value: Value) -> Result<Value, String> {
+        let count = value.get("count").and_then(|c| c.as_u64()).ok_or("Expected a count")?;
+        value["count"] = Value::from(count * 2);
+        Ok(value)
+    }
+
+    const PAYLOAD: VersionedPayload = VersionedPayload {
+        key: "test",
+        current_version: 2,
+        migrations: &[add_field, double_count],
+    };
+
+    #[test]
+    fn migrates_from_stored_version() {
+        let value = serde_json::json!({ "count": 1 });
+        assert_eq!(PAYLOAD.migrate(value.clone(), 0).unwrap(), serde_json::json!({ "count": 2, "added": true }));
+        // Only later migrations run
+        assert_eq!(PAYLOAD.migrate(value.clone(), 1).unwrap(), serde_json::json!({ "count": 2 }));
+        assert_eq!(PAYLOAD.migrate(value.clone(), 2).unwrap(), value);
+        // Newer than supported: left as it is
+        assert_eq!(PAYLOAD.migrate(value.clone(), 3).unwrap(), value);
+    }
+
+    #[test]
+    fn failed_migration_names_version() {
+        let error = PAYLOAD.migrate(serde_json::json!([]), 0).unwrap_err();
+        assert!(error.contains("from schema version 0"), "{}", error);
+        let error = PAYLOAD.migrate(serde_json::json!({}), 1).unwrap_err();
+        assert!(error.contains("from schema version 1"), "{}", error);
+    }
+}
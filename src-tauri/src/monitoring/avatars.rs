@@ -2,7 +2,7 @@ use parking_lot::Mutex;
 
 use tauri::{AppHandle, Manager};
 
-use crate::{api::avatar_search::update_avatar, memory::users::Users, monitoring::VrcLogEntry};
+use crate::{api::{avatar_search::update_avatar, user::AdvisoryTrigger}, memory::users::Users, monitoring::VrcLogEntry};
 
 pub fn handle_switched_avatar(app: AppHandle, line: &VrcLogEntry) -> Result<bool, tauri::Error> {
     // Determine if this is an instance join line
@@ -47,6 +47,10 @@ pub fn handle_switched_avatar(app: AppHandle, line: &VrcLogEntry) -> Result<bool
                         .iter_mut()
                         .find(|user| user.username == username) {
                         user.avatar_name = avatar_name.clone();
+                        // What we knew about the previous avatar no longer applies
+                        user.avatar_id = None;
+                        user.avatar_release_status = None;
+                        user.avatar_candidates.clear();
                     } else {
                         eprintln!("User '{}' not found in user list after initial check", username);
                         return Ok(false);
@@ -69,7 +73,7 @@ pub fn handle_switched_avatar(app: AppHandle, line: &VrcLogEntry) -> Result<bool
                 } else {
                     drop(users_state); // Release the lock before doing async work
                     drop(instance_state);
-                    update_avatar(user.clone(), app.clone());
+                    update_avatar(user.clone(), app.clone(), AdvisoryTrigger::AvatarSwitched);
                 }
                 // if let Some(user) = found_user {
                 //     // Emit an event
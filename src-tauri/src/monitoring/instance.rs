@@ -4,6 +4,7 @@ use tauri::http::response;
 use tauri::{AppHandle, Emitter, Manager};
 use vrchatapi::models::InstanceType;
 
+use crate::advisories::profiles::switch_advisory_profile;
 use crate::api::VrchatApiStateMutex;
 use crate::settings;
 use crate::{
@@ -38,7 +39,7 @@ pub fn handle_joined_instance(app: AppHandle, line: &VrcLogEntry) -> Result<bool
         {
             let advisory_memory = app.state::<Mutex<crate::memory::advisories::AdvisoryMemory>>();
             let mut advisory_memory = advisory_memory.lock();
-            advisory_memory.notices.clear();
+            advisory_memory.clear_notices();
         }
         // Clear the user list when joining a new instance
         {
@@ -54,6 +55,9 @@ pub fn handle_joined_instance(app: AppHandle, line: &VrcLogEntry) -> Result<bool
             //avatar_state.possible_avatar_files.clear(); // actually, keep these (this is a cache)
             avatar_state.pending_file_metadata_lookups.clear();
         }
+        // Switch to the advisory profile for this instance (this needs the instance state)
+        drop(state);
+        switch_advisory_profile(app.clone());
         // Emit an event
         app.emit("vrcmrd:instance", instance_id.to_string())?;
         return Ok(true);
@@ -159,7 +163,7 @@ pub fn query_instance_info(app: AppHandle, instance_id: &VrcMrdInstanceId) {
                                     let updated_user = user.update_from(
                                         handle.clone(),
                                         &CommonUser::from(member.clone()),
-                                        Vec::new(),
+                                        None,
                                     );
                                     *user = updated_user.clone();
                                     break; // from inner loop
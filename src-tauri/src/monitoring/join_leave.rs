@@ -1,6 +1,7 @@
 use parking_lot::Mutex;
 
 use crate::api::user::thread_query_user_info;
+use crate::memory::moderation::ModerationHistory;
 use crate::memory::users::Users;
 use tauri::{AppHandle, Emitter, Manager};
 
@@ -39,6 +40,10 @@ pub fn handle_join_leave(app: AppHandle, line: &VrcLogEntry) -> Result<bool, tau
                         None
                     }
                 };
+                let recently_kicked = app
+                    .state::<Mutex<ModerationHistory>>()
+                    .lock()
+                    .recently_moderated(&player_id);
                 let user = VrcMrdUser {
                     id: player_id,
                     username: player_name,
@@ -46,18 +51,31 @@ pub fn handle_join_leave(app: AppHandle, line: &VrcLogEntry) -> Result<bool, tau
                     avatar_id: None,
                     avatar_images: vec![],
                     avatar_creator: None,
+                    avatar_release_status: None,
+                    avatar_candidates: vec![],
                     perf_rank: None,
                     pronouns: None,
                     status: None,
+                    bio: None,
+                    bio_links: vec![],
                     account_created: None,
                     join_time: parse_timestamp(&line.timestamp),
                     leave_time: None,
                     advisories: vec![], // this should contain the actual advisories
                     age_verified: false,
+                    is_friend: false,
+                    is_group_staff: false,
                     platform: None,
                     trust_rank: None,
-                    recently_kicked: false,
+                    tags: vec![],
+                    flags: Default::default(),
+                    recently_kicked,
                     groups: vec![],
+                    groups_known: false,
+                    info_loaded: false,
+                    risk_score: 0,
+                    effective_level: None,
+                    escalated_by: None,
                 };
                 let state = app.state::<Mutex<Users>>();
                 let mut state = state.lock();
@@ -105,19 +123,32 @@ pub fn handle_join_leave(app: AppHandle, line: &VrcLogEntry) -> Result<bool, tau
                         avatar_name: String::new(),
                         avatar_id: None,
                         avatar_creator: None,
+                        avatar_release_status: None,
+                        avatar_candidates: vec![],
                         avatar_images: vec![],
                         perf_rank: None,
                         pronouns: None,
                         status: None,
+                        bio: None,
+                        bio_links: vec![],
                         account_created: None,
                         join_time: 0, // TODO: store it as a unix timestamp and format on frontend
                         leave_time: Some(leave_time),
                         trust_rank: None,
+                        tags: vec![],
+                        flags: Default::default(),
                         advisories: vec![], // this should contain the actual advisories
                         age_verified: false,
+                        is_friend: false,
+                        is_group_staff: false,
                         platform: None,
                         recently_kicked: false,
                         groups: vec![],
+                        groups_known: false,
+                        info_loaded: false,
+                        risk_score: 0,
+                        effective_level: None,
+                        escalated_by: None,
                     };
                     state.inner.push(user.clone());
                 }
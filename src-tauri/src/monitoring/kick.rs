@@ -4,40 +4,51 @@
 use parking_lot::Mutex;
 use tauri::{AppHandle, Manager};
 
-use crate::{memory::users::Users, monitoring::VrcLogEntry};
+use crate::{
+    memory::{
+        moderation::{record_moderation_event, ModerationEvent, ModerationKind},
+        users::Users,
+    },
+    monitoring::{join_leave::parse_timestamp, VrcLogEntry},
+};
 
 pub fn handle_kick(app: AppHandle, line: &VrcLogEntry) -> Result<bool, tauri::Error> {
     let message = &line.message;
     if let Some(rest) = message.strip_prefix("[ModerationManager] ") {
-        // TODO: also support "has been banned"?
-        if let Some(kick_info) = rest.strip_suffix(" has been kicked") {
-            let username = kick_info.trim();
-            println!("User kicked: {}", username);
-            // Mark the user as recently kicked in the memory
-            let state = app.state::<Mutex<Users>>();
-            let mut state = state.lock();
-            if let Some(user) = state.inner.iter_mut().find(|u| u.username == username) {
-                user.recently_kicked = true;
-                println!("Marked user '{}' as recently kicked", username);
-            } else {
-                println!("Could not find user '{}' to mark as recently kicked", username);
-            }
-            return Ok(true);
+        let (username, kind) = if let Some(kick_info) = rest.strip_suffix(" has been kicked") {
+            (kick_info.trim(), ModerationKind::Kick)
         } else if let Some(ban_info) = rest.strip_suffix(" has been banned") {
             // NOTE: this is untested because I haven't personally banned anyone lol
-            let username = ban_info.trim();
-            println!("User banned: {}", username);
-            // Mark the user as recently kicked in the memory
+            (ban_info.trim(), ModerationKind::Ban)
+        } else {
+            return Ok(false);
+        };
+        println!("User {:?}: {}", kind, username);
+        // Mark the user as recently kicked in the memory
+        let user_id = {
             let state = app.state::<Mutex<Users>>();
             let mut state = state.lock();
             if let Some(user) = state.inner.iter_mut().find(|u| u.username == username) {
                 user.recently_kicked = true;
-                println!("Marked user '{}' as recently banned", username);
+                println!("Marked user '{}' as recently kicked", username);
+                Some(user.id.clone())
             } else {
-                println!("Could not find user '{}' to mark as recently banned", username);
+                println!("Could not find user '{}' to mark as recently kicked", username);
+                None
             }
-            return Ok(true);
+        };
+        // Keep the event by user ID, so it still applies if they rejoin
+        if let Some(user_id) = user_id {
+            record_moderation_event(
+                app.clone(),
+                ModerationEvent {
+                    user_id,
+                    kind,
+                    at: parse_timestamp(&line.timestamp),
+                },
+            );
         }
+        return Ok(true);
     }
     Ok(false)
-}
\ No newline at end of file
+}
@@ -0,0 +1,373 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Read, Seek, SeekFrom, Write},
+    path::PathBuf,
+    time::Duration,
+};
+
+use parking_lot::Mutex;
+use tauri::{Manager, Runtime, Wry};
+use tokio::{
+    io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt, BufWriter},
+    sync::mpsc::{self, UnboundedReceiver, UnboundedSender},
+};
+
+use crate::{
+    memory::advisories::AdvisoryMemory,
+    settings::get_config,
+    types::{
+        advisories::Notice,
+        notices::{NoticePage, NoticeQuery, StoredNotice},
+    },
+};
+
+/// How many days to keep notices in History for. 0 keeps them forever.
+pub const NOTICE_RETENTION_CONFIG_KEY: &str = "notice_retention_days";
+pub const DEFAULT_NOTICE_RETENTION_DAYS: u32 = 30;
+/// The History file, in the app's local data directory. One [StoredNotice] per line, oldest first.
+const NOTICE_HISTORY_FILE: &str = "notice_history.jsonl";
+const DEFAULT_PAGE_SIZE: usize = 50;
+/// How often to drop notices older than the retention period.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Where a notice is in the History file, with the fields History can be filtered by.
+/// Filtering only reads the index; notices are only read from disk for the requested page.
+#[derive(Clone)]
+struct IndexEntry {
+    id: u64,
+    revision: u64,
+    offset: u64,
+    len: usize,
+    recorded_at: i64,
+    level: u8,
+    user_id: Option<String>,
+    advisory_id: Option<String>,
+    instance_id: Option<String>,
+}
+
+impl IndexEntry {
+    fn new(offset: u64, len: usize, stored: &StoredNotice) -> Self {
+        Self {
+            id: stored.notice.id,
+            revision: stored.notice.revision,
+            offset,
+            len,
+            recorded_at: stored.recorded_at,
+            level: stored.notice.level.clone() as u8,
+            user_id: stored.notice.relevant_user_id.clone(),
+            advisory_id: stored.notice.relevant_advisory_id.clone(),
+            instance_id: stored.instance_id.clone(),
+        }
+    }
+    fn matches(&self, query: &NoticeQuery) -> bool {
+        query.min_level.as_ref().is_none_or(|level| self.level >= level.clone() as u8)
+            && query.user_id.as_ref().is_none_or(|id| self.user_id.as_ref() == Some(id))
+            && query.advisory_id.as_ref().is_none_or(|id| self.advisory_id.as_ref() == Some(id))
+            && query.instance_id.as_ref().is_none_or(|id| self.instance_id.as_ref() == Some(id))
+            && query.from.is_none_or(|from| self.recorded_at >= from)
+            && query.to.is_none_or(|to| self.recorded_at < to)
+    }
+}
+
+/// Notices from every instance, kept on disk so they survive instance changes and restarts.
+/// [crate::memory::advisories::AdvisoryMemory::notices] only holds the current instance's notices.
+///
+/// A notice that changes after it was recorded (a collapsed repeat or a state change) is appended again;
+/// the index points at its latest line, and pruning drops the older ones.
+#[derive(Default)]
+pub struct NoticeHistory {
+    /// `None` until the app's data directory is resolved.
+    path: Option<PathBuf>,
+    /// One entry per notice, in ID order.
+    index: Vec<IndexEntry>,
+}
+
+/// Work for the History writer task, so publishing a notice never waits on the disk.
+enum HistoryWrite {
+    Append(StoredNotice),
+    /// A newer revision of a notice that's already in History.
+    Update(Notice),
+    Prune(u32),
+}
+
+/// Sends work to the History writer task. See [run_writer].
+pub struct NoticeHistoryWriter(UnboundedSender<HistoryWrite>);
+
+impl NoticeHistory {
+    /// Read the History file and build the index. Lines that can't be read are skipped.
+    /// If the last line was cut off (i.e. by a crash mid-write), it's ended so the next notice starts on its own line.
+    fn load(&mut self, path: PathBuf) -> Result<(), String> {
+        self.path = Some(path.clone());
+        self.index.clear();
+        let file = match File::open(&path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e.to_string()),
+        };
+        let mut reader = BufReader::new(file);
+        let mut offset = 0;
+        let mut line = String::new();
+        let mut torn = false;
+        loop {
+            line.clear();
+            let len = reader.read_line(&mut line).map_err(|e| e.to_string())?;
+            if len == 0 {
+                break;
+            }
+            // Only the last line can be missing its newline
+            torn = !line.ends_with('\n');
+            match serde_json::from_str::<StoredNotice>(line.trim_end()) {
+                Ok(stored) => self.insert(IndexEntry::new(offset, len, &stored)),
+                Err(e) => eprintln!("Skipping unreadable notice in History at byte {}: {}", offset, e),
+            }
+            offset += len as u64;
+        }
+        if torn {
+            eprintln!("The last notice in History was cut off, ending its line");
+            let mut file = OpenOptions::new().append(true).open(&path).map_err(|e| e.to_string())?;
+            file.write_all(b"\n").map_err(|e| e.to_string())?;
+            if let Some(last) = self.index.iter_mut().find(|e| e.offset + e.len as u64 == offset) {
+                last.len += 1;
+            }
+        }
+        Ok(())
+    }
+
+    /// Add a notice's entry, replacing the entry for an earlier revision of it.
+    fn insert(&mut self, entry: IndexEntry) {
+        match self.index.binary_search_by_key(&entry.id, |e| e.id) {
+            Ok(position) => self.index[position] = entry,
+            Err(position) => self.index.insert(position, entry),
+        }
+    }
+
+    fn get(&self, id: u64) -> Option<&IndexEntry> {
+        self.index.binary_search_by_key(&id, |e| e.id).ok().map(|position| &self.index[position])
+    }
+
+    fn query(&self, query: &NoticeQuery) -> Result<NoticePage, String> {
+        let limit = if query.limit == 0 { DEFAULT_PAGE_SIZE } else { query.limit };
+        let matching: Vec<&IndexEntry> = self.index.iter().rev().filter(|e| e.matches(query)).collect();
+        let page: Vec<&IndexEntry> = matching.iter().skip(query.offset).take(limit).copied().collect();
+        let mut notices = Vec::with_capacity(page.len());
+        if !page.is_empty() {
+            let path = self.path.as_ref().ok_or("Notice history isn't loaded yet")?;
+            let mut file = File::open(path).map_err(|e| e.to_string())?;
+            for entry in page {
+                let mut buffer = vec![0; entry.len];
+                file.seek(SeekFrom::Start(entry.offset)).map_err(|e| e.to_string())?;
+                file.read_exact(&mut buffer).map_err(|e| e.to_string())?;
+                match serde_json::from_slice::<StoredNotice>(&buffer) {
+                    Ok(stored) => notices.push(stored),
+                    Err(e) => eprintln!("Skipping unreadable notice in History at byte {}: {}", entry.offset, e),
+                }
+            }
+        }
+        Ok(NoticePage {
+            notices,
+            total: matching.len(),
+        })
+    }
+}
+
+pub fn notice_history_plugin<R: Runtime>() -> tauri::plugin::TauriPlugin<R> {
+    tauri::plugin::Builder::new("notice_history")
+        .setup(|app, _api| {
+            // Load History before anything can publish a notice, so notice IDs continue from the last session
+            // and no notice is appended before the index is built
+            let mut history = NoticeHistory::default();
+            match app.path().app_local_data_dir() {
+                Ok(dir) => {
+                    if let Err(e) = std::fs::create_dir_all(&dir) {
+                        eprintln!("Failed to create the app data directory for History: {}", e);
+                    }
+                    if let Err(e) = history.load(dir.join(NOTICE_HISTORY_FILE)) {
+                        eprintln!("Failed to load notice history: {}", e);
+                    }
+                }
+                Err(e) => eprintln!("Failed to resolve the app data directory for History: {}", e),
+            }
+            // Revisions can be ahead of IDs, i.e. after a notice was acknowledged
+            let last_id = history.index.iter().map(|e| e.id.max(e.revision)).max().unwrap_or_default();
+            app.manage(Mutex::new(history));
+            {
+                // Continue notice IDs from the last session, so they stay unique in History
+                let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+                let mut advisory_memory = advisory_memory.lock();
+                if advisory_memory.notice_sequence < last_id {
+                    advisory_memory.notice_sequence = last_id;
+                    advisory_memory.notices_cleared_at = last_id;
+                }
+            }
+            let (sender, receiver) = mpsc::unbounded_channel();
+            app.manage(NoticeHistoryWriter(sender.clone()));
+            tauri::async_runtime::spawn(run_writer(app.clone(), receiver));
+            let app_clone = app.clone();
+            tauri::async_runtime::spawn(async move {
+                loop {
+                    let retention_days = retention_days(app_clone.clone()).await;
+                    if sender.send(HistoryWrite::Prune(retention_days)).is_err() {
+                        break;
+                    }
+                    tokio::time::sleep(PRUNE_INTERVAL).await;
+                }
+            });
+            Ok(())
+        })
+        .build()
+}
+
+async fn retention_days<R: Runtime>(app: tauri::AppHandle<R>) -> u32 {
+    get_config(app, NOTICE_RETENTION_CONFIG_KEY.to_string())
+        .await
+        .ok()
+        .flatten()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_NOTICE_RETENTION_DAYS)
+}
+
+/// Write History on its own task, one write at a time, so the file only changes here.
+/// Keeps one append handle open, reopening it after pruning replaces the file or a write fails.
+async fn run_writer<R: Runtime>(app: tauri::AppHandle<R>, mut receiver: UnboundedReceiver<HistoryWrite>) {
+    let mut file: Option<tokio::fs::File> = None;
+    while let Some(write) = receiver.recv().await {
+        let result = match write {
+            HistoryWrite::Append(stored) => append(&app, &mut file, &stored).await,
+            HistoryWrite::Update(notice) => {
+                // Keep where and when the notice was first raised
+                let stored = {
+                    let history = app.state::<Mutex<NoticeHistory>>();
+                    let history = history.lock();
+                    history.get(notice.id).map(|entry| StoredNotice {
+                        instance_id: entry.instance_id.clone(),
+                        recorded_at: entry.recorded_at,
+                        notice,
+                    })
+                };
+                match stored {
+                    Some(stored) => append(&app, &mut file, &stored).await,
+                    // Already pruned
+                    None => Ok(()),
+                }
+            }
+            HistoryWrite::Prune(retention_days) => {
+                file = None;
+                prune(&app, retention_days).await
+            }
+        };
+        if let Err(e) = result {
+            file = None;
+            eprintln!("Failed to write notice history: {}", e);
+        }
+    }
+}
+
+async fn append<R: Runtime>(
+    app: &tauri::AppHandle<R>,
+    file: &mut Option<tokio::fs::File>,
+    stored: &StoredNotice,
+) -> Result<(), String> {
+    let history = app.state::<Mutex<NoticeHistory>>();
+    let Some(path) = history.lock().path.clone() else {
+        return Err("Notice history isn't loaded yet".to_string());
+    };
+    let file = match file {
+        Some(file) => file,
+        None => file.insert(
+            tokio::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .await
+                .map_err(|e| e.to_string())?,
+        ),
+    };
+    let mut line = serde_json::to_string(stored).map_err(|e| e.to_string())?;
+    line.push('\n');
+    let offset = file.metadata().await.map_err(|e| e.to_string())?.len();
+    file.write_all(line.as_bytes()).await.map_err(|e| e.to_string())?;
+    // Queries read the file directly, so the line has to be there before it's indexed
+    file.flush().await.map_err(|e| e.to_string())?;
+    history.lock().insert(IndexEntry::new(offset, line.len(), stored));
+    Ok(())
+}
+
+/// Drop notices older than the retention period (and lines for earlier revisions) by rewriting the file without them.
+async fn prune<R: Runtime>(app: &tauri::AppHandle<R>, retention_days: u32) -> Result<(), String> {
+    if retention_days == 0 {
+        return Ok(());
+    }
+    let cutoff = chrono::Utc::now().timestamp() - retention_days as i64 * 24 * 60 * 60;
+    let (path, kept, pruned) = {
+        let history = app.state::<Mutex<NoticeHistory>>();
+        let history = history.lock();
+        let Some(path) = history.path.clone() else {
+            return Ok(());
+        };
+        let kept: Vec<IndexEntry> = history.index.iter().filter(|e| e.recorded_at >= cutoff).cloned().collect();
+        let pruned = history.index.len() - kept.len();
+        (path, kept, pruned)
+    };
+    if pruned == 0 {
+        return Ok(());
+    }
+    let temp_path = path.with_extension("jsonl.tmp");
+    let mut index = Vec::with_capacity(kept.len());
+    {
+        let mut source = tokio::fs::File::open(&path).await.map_err(|e| e.to_string())?;
+        let mut target = BufWriter::new(tokio::fs::File::create(&temp_path).await.map_err(|e| e.to_string())?);
+        let mut offset = 0;
+        for entry in kept {
+            let mut buffer = vec![0; entry.len];
+            source.seek(SeekFrom::Start(entry.offset)).await.map_err(|e| e.to_string())?;
+            source.read_exact(&mut buffer).await.map_err(|e| e.to_string())?;
+            target.write_all(&buffer).await.map_err(|e| e.to_string())?;
+            index.push(IndexEntry { offset, ..entry });
+            offset += buffer.len() as u64;
+        }
+        target.flush().await.map_err(|e| e.to_string())?;
+    }
+    {
+        // Nothing else writes the file, but queries read it, so swap the file and the index together.
+        // Renaming is a single quick call, unlike the rewrite above.
+        let history = app.state::<Mutex<NoticeHistory>>();
+        let mut history = history.lock();
+        std::fs::rename(&temp_path, &path).map_err(|e| e.to_string())?;
+        history.index = index;
+    }
+    println!("Pruned {} notices older than {} days from History", pruned, retention_days);
+    Ok(())
+}
+
+/// Add a notice to History, tagged with the instance it was raised in.
+/// The write happens in the background; failures are logged.
+pub fn record_notice(app: &tauri::AppHandle<Wry>, notice: &Notice, instance_id: Option<String>) {
+    send(
+        app,
+        HistoryWrite::Append(StoredNotice {
+            instance_id,
+            recorded_at: chrono::Utc::now().timestamp(),
+            notice: notice.clone(),
+        }),
+    );
+}
+
+/// Record a newer revision of a notice in History, i.e. after a repeat was collapsed into it or its state changed.
+pub fn record_notice_update(app: &tauri::AppHandle<Wry>, notice: &Notice) {
+    send(app, HistoryWrite::Update(notice.clone()));
+}
+
+fn send(app: &tauri::AppHandle<Wry>, write: HistoryWrite) {
+    let writer = app.state::<NoticeHistoryWriter>();
+    if writer.0.send(write).is_err() {
+        eprintln!("The notice history writer has stopped");
+    }
+}
+
+/// Query History, newest first.
+#[tauri::command]
+pub async fn query_notice_history(app: tauri::AppHandle<Wry>, query: NoticeQuery) -> Result<NoticePage, String> {
+    let history = app.state::<Mutex<NoticeHistory>>();
+    let history = history.lock();
+    history.query(&query)
+}
@@ -1,13 +1,18 @@
 use crate::{
+    notice_history::{record_notice, record_notice_update},
     api::xsoverlay::{XSO_CONNECTED, queue_xsoverlay_command}, memory::advisories::AdvisoryMemory, settings::get_config, types::{
-        advisories::{AdvisoryLevel, Notice},
+        advisories::{AdvisoryLevel, Notice, NoticeState},
+        notices::{NoticeCursorPage, NoticeDelta},
         xsoverlay::{XSOverlayCommand, XSOverlayNotificationObject},
     }
 };
 use parking_lot::Mutex;
 use tauri_plugin_tts::{SpeakRequest, TtsExt};
 use std::{
-    ops::{Deref, DerefMut}
+    collections::HashMap,
+    ops::{Deref, DerefMut},
+    sync::LazyLock,
+    time::Duration,
 };
 use tauri::{Emitter, Manager, Wry};
 #[cfg(target_os = "windows")]
@@ -17,25 +22,100 @@ use windows::{
     Win32::UI::Shell::{GetCurrentProcessExplicitAppUserModelID, SetCurrentProcessExplicitAppUserModelID}, core::PCWSTR
 };
 
-// TODO: chunk notice list, so the UI doesn't have to load them all at once when it opens the notices tab
+const DEFAULT_NOTICE_PAGE_SIZE: usize = 50;
+/// Whether acknowledging, claiming or resolving a notice skips its TTS (if not queued yet) and further alerts for repeats. Off by default.
+pub const ACKNOWLEDGE_CANCELS_ALERTS_CONFIG_KEY: &str = "acknowledge_cancels_alerts";
+/// How long to collect similar alerts into one digest, in seconds. 0 sends every alert on its own.
+pub const ALERT_BATCH_WINDOW_CONFIG_KEY: &str = "alert_batch_window_seconds";
+pub const DEFAULT_ALERT_BATCH_WINDOW_SECONDS: u64 = 3;
+/// How many notices a digest lists before summarising the rest.
+const DIGEST_MAX_LINES: usize = 5;
+/// The name recorded when this moderator acknowledges, claims or resolves a notice without giving one.
+pub const MODERATOR_NAME_CONFIG_KEY: &str = "moderator_name";
+
+/// Get a page of the current instance's notices, newest first.
+/// Leave `before` unset for the newest notices, then pass the returned `next_cursor` to get older ones.
 #[tauri::command]
-pub async fn get_all_notices(app: tauri::AppHandle<Wry>) -> Result<Vec<Notice>, String> {
-    let notices = app
-        .state::<Mutex<AdvisoryMemory>>()
-        .lock()
+pub async fn get_notices(
+    app: tauri::AppHandle<Wry>,
+    before: Option<u64>,
+    limit: Option<usize>,
+) -> Result<NoticeCursorPage, String> {
+    let limit = limit.filter(|l| *l > 0).unwrap_or(DEFAULT_NOTICE_PAGE_SIZE);
+    let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+    let advisory_memory = advisory_memory.lock();
+    // Notices are kept in the order they were published, so IDs are ascending
+    let mut older = advisory_memory
         .deref()
         .notices
-        .clone();
-    Ok(notices)
+        .iter()
+        .rev()
+        .filter(|n| before.is_none_or(|before| n.id < before));
+    let notices: Vec<Notice> = older.by_ref().take(limit).cloned().collect();
+    let next_cursor = match older.next() {
+        Some(_) => notices.last().map(|n| n.id),
+        None => None,
+    };
+    Ok(NoticeCursorPage {
+        notices,
+        next_cursor,
+        cursor: advisory_memory.notice_sequence,
+        session: advisory_memory.notice_session.clone(),
+    })
+}
+
+/// Get the notices that were published or changed after `since` (a cursor from [get_notices] or an earlier call),
+/// so clients don't have to fetch the whole list again. `session` is the session the cursor came with.
+#[tauri::command]
+pub async fn get_notices_since(app: tauri::AppHandle<Wry>, since: u64, session: String) -> Result<NoticeDelta, String> {
+    let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+    let advisory_memory = advisory_memory.lock();
+    Ok(notices_since(&advisory_memory, since, &session))
 }
 
-pub fn publish_notice(app: tauri::AppHandle<Wry>, notice: Notice) -> Result<(), String> {
-    {
+fn notices_since(advisory_memory: &AdvisoryMemory, since: u64, session: &str) -> NoticeDelta {
+    // A cursor from before the last clear, or from before a restart, is stale
+    let reset = session != advisory_memory.notice_session
+        || since < advisory_memory.notices_cleared_at
+        || since > advisory_memory.notice_sequence;
+    let mut notices: Vec<Notice> = advisory_memory
+        .notices
+        .iter()
+        .filter(|n| reset || n.revision > since)
+        .cloned()
+        .collect();
+    notices.sort_by_key(|n| n.revision);
+    NoticeDelta {
+        notices,
+        cursor: advisory_memory.notice_sequence,
+        session: advisory_memory.notice_session.clone(),
+        reset,
+    }
+}
+
+/// Add a notice to the notice list and History, and alert for it. `instance_id` is the instance the notice
+/// was raised in, for History.
+/// Returns `false` if the notice was a repeat within its advisory's cooldown (or of a silenced notice),
+/// so the caller can skip anything else that should only happen once per alert.
+pub fn publish_notice(app: tauri::AppHandle<Wry>, notice: Notice, instance_id: Option<String>) -> Result<bool, String> {
+    let (notice, repeat) = match apply_cooldown(app.clone(), notice)? {
+        (Some(notice), repeat) => (notice, repeat),
+        (None, _) => return Ok(false),
+    };
+    let notice = {
         // Add the notice to memory (where the UI can find it)
         let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
         let mut advisory_memory = advisory_memory.lock();
+        let id = advisory_memory.next_notice_sequence();
+        let notice = Notice {
+            id,
+            revision: id,
+            ..notice
+        };
         advisory_memory.deref_mut().notices.push(notice.clone());
-    }
+        notice
+    };
+    record_notice(&app, &notice, instance_id);
     // Emit an event so the UI can react immediately (show toast, update notice list)
     app.emit("vrcmrd:notice", notice.clone())
         .map_err(|e| e.to_string())?;
@@ -47,6 +127,7 @@ pub fn publish_notice(app: tauri::AppHandle<Wry>, notice: Notice) -> Result<(),
         let users_state = app.state::<Mutex<crate::memory::users::Users>>();
         let users_state = users_state.try_lock_for(std::time::Duration::from_secs(2));
         if let Some(users_state) = users_state {
+            #[cfg(debug_assertions)]
             eprintln!("[DEBUG] Checking if notice is relevant for user. Notice relevant_user_id: {:?}, users_state.joined_before_settled: {:?}", notice.relevant_user_id, users_state.joined_before_settled.join(", "));
             if notice.relevant_user_id.is_some() && users_state.joined_before_settled.contains(&notice.clone().relevant_user_id.unwrap()) {
                 println!("Skipping notification for user {} because they joined before me", notice.relevant_user_id.unwrap());
@@ -71,7 +152,166 @@ pub fn publish_notice(app: tauri::AppHandle<Wry>, notice: Notice) -> Result<(),
         }
     };
 
-    if notice.send_notification && settled_for_user && user_in_instance {
+    queue_alert(
+        app,
+        PendingAlert {
+            notification: notice.send_notification && settled_for_user && user_in_instance,
+            tts: notice.send_tts && settled_for_user,
+            notice,
+        },
+    );
+    Ok(!repeat)
+}
+
+/// A notice waiting to alert, with the outputs it should alert on.
+struct PendingAlert {
+    notice: Notice,
+    notification: bool,
+    tts: bool,
+}
+
+/// Alerts are batched by advisory if the notice has one, otherwise by level.
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum AlertBatchKey {
+    Advisory(String),
+    Level(u8),
+}
+
+impl AlertBatchKey {
+    fn of(notice: &Notice) -> Self {
+        match &notice.relevant_advisory_id {
+            Some(advisory_id) => Self::Advisory(advisory_id.clone()),
+            None => Self::Level(notice.level.clone() as u8),
+        }
+    }
+}
+
+/// Alerts waiting for their batch window to end. See [queue_alert].
+static PENDING_ALERTS: LazyLock<Mutex<HashMap<AlertBatchKey, Vec<PendingAlert>>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Alert for a notice, batching it with similar notices that arrive within [ALERT_BATCH_WINDOW_CONFIG_KEY],
+/// so a raid doesn't mean one popup and one TTS line per user. Maximum-level notices always alert immediately.
+fn queue_alert(app: tauri::AppHandle<Wry>, alert: PendingAlert) {
+    if !alert.notification && !alert.tts {
+        return;
+    }
+    if alert.notice.level == AdvisoryLevel::Maximum {
+        send_alert(app, alert);
+        return;
+    }
+    tauri::async_runtime::spawn(async move {
+        let window = alert_batch_window(app.clone()).await;
+        if window == 0 {
+            send_alert(app, alert);
+            return;
+        }
+        let key = AlertBatchKey::of(&alert.notice);
+        let opens_batch = {
+            let mut pending = PENDING_ALERTS.lock();
+            let batch = pending.entry(key.clone()).or_default();
+            batch.push(alert);
+            batch.len() == 1
+        };
+        // The first alert in a batch waits out the window, then sends everything that joined it
+        if !opens_batch {
+            return;
+        }
+        tokio::time::sleep(Duration::from_secs(window)).await;
+        let mut batch = PENDING_ALERTS.lock().remove(&key).unwrap_or_default();
+        if cancels_alerts(app.clone()).await {
+            batch.retain(|alert| !is_handled(&app, alert.notice.id));
+        }
+        // Notifications and TTS are sent separately, so each digest only covers the notices that asked for it
+        let notifications: Vec<PendingAlert> = batch
+            .iter()
+            .filter(|alert| alert.notification)
+            .map(|alert| PendingAlert { notice: alert.notice.clone(), notification: true, tts: false })
+            .collect();
+        let speech: Vec<PendingAlert> = batch
+            .into_iter()
+            .filter(|alert| alert.tts)
+            .map(|alert| PendingAlert { notification: false, ..alert })
+            .collect();
+        send_batch(&app, &key, notifications);
+        send_batch(&app, &key, speech);
+    });
+}
+
+/// Send a batch of alerts: on its own if there's only one, otherwise as a digest.
+fn send_batch(app: &tauri::AppHandle<Wry>, key: &AlertBatchKey, mut batch: Vec<PendingAlert>) {
+    match batch.len() {
+        0 => {}
+        1 => send_alert(app.clone(), batch.remove(0)),
+        _ => send_alert(app.clone(), digest_alert(app, key, &batch)),
+    }
+}
+
+async fn alert_batch_window(app: tauri::AppHandle<Wry>) -> u64 {
+    get_config(app, ALERT_BATCH_WINDOW_CONFIG_KEY.to_string())
+        .await
+        .ok()
+        .flatten()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_ALERT_BATCH_WINDOW_SECONDS)
+}
+
+/// One alert for a batch, i.e. "5 users matched Crasher Group X". The digest is only an alert;
+/// each notice is still in the notice list on its own. It alerts on the outputs the batch's alerts asked for.
+fn digest_alert(app: &tauri::AppHandle<Wry>, key: &AlertBatchKey, batch: &[PendingAlert]) -> PendingAlert {
+    let mut user_ids: Vec<&Option<String>> = batch.iter().map(|a| &a.notice.relevant_user_id).collect();
+    user_ids.sort();
+    user_ids.dedup();
+    let level = batch
+        .iter()
+        .map(|a| a.notice.level.clone())
+        .max_by_key(|level| level.clone() as u8)
+        .unwrap_or(AdvisoryLevel::None);
+    let title = match key {
+        AlertBatchKey::Advisory(advisory_id) => {
+            let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+            let advisory_memory = advisory_memory.lock();
+            let name = advisory_memory
+                .all_advisories
+                .iter()
+                .find(|a| a.id == *advisory_id)
+                .map(|a| a.name.clone())
+                .unwrap_or("an advisory".to_string());
+            match user_ids.len() {
+                1 => format!("1 user matched {}", name),
+                count => format!("{} users matched {}", count, name),
+            }
+        }
+        AlertBatchKey::Level(_) => format!("{} {}-level notices", batch.len(), format!("{:?}", level).to_lowercase()),
+    };
+    let mut lines: Vec<String> = batch
+        .iter()
+        .take(DIGEST_MAX_LINES)
+        .map(|a| a.notice.title.clone().unwrap_or(a.notice.message.clone()))
+        .collect();
+    if batch.len() > DIGEST_MAX_LINES {
+        lines.push(format!("and {} more", batch.len() - DIGEST_MAX_LINES));
+    }
+    PendingAlert {
+        notice: Notice {
+            title: Some(title),
+            message: lines.join("\n"),
+            level,
+            relevant_advisory_id: match key {
+                AlertBatchKey::Advisory(advisory_id) => Some(advisory_id.clone()),
+                AlertBatchKey::Level(_) => None,
+            },
+            created_at: Some(chrono::Utc::now().to_rfc3339()),
+            ..Default::default()
+        },
+        notification: batch.iter().any(|a| a.notification),
+        tts: batch.iter().any(|a| a.tts),
+    }
+}
+
+/// Send a notice's alerts (XSOverlay or desktop notification, and TTS), subject to the notification and TTS preferences.
+fn send_alert(app: tauri::AppHandle<Wry>, alert: PendingAlert) {
+    let notice = alert.notice;
+    if alert.notification {
         let notice = notice.clone();
         let app = app.clone();
         tauri::async_runtime::spawn(async move {
@@ -158,7 +398,7 @@ pub fn publish_notice(app: tauri::AppHandle<Wry>, notice: Notice) -> Result<(),
             // TODO: send TTS if applicable (TTS has its own thread, so dispatch to that)
         });
     }
-    if notice.send_tts && settled_for_user {
+    if alert.tts {
         let notice = notice.clone();
         tauri::async_runtime::spawn(async move {
             let tts_preference = get_config(app.clone(), "tts_preference".to_string())
@@ -169,6 +409,10 @@ pub fn publish_notice(app: tauri::AppHandle<Wry>, notice: Notice) -> Result<(),
             if tts_preference == 0 {
                 return;
             }
+            if is_handled(&app, notice.id) && cancels_alerts(app.clone()).await {
+                println!("Skipping TTS for notice {} because it was already handled", notice.id);
+                return;
+            }
             let tts = app.tts();
             // let voices = tts.get_voices(GetVoicesRequest {
             //     language: Some("en_US".to_string())
@@ -189,7 +433,222 @@ pub fn publish_notice(app: tauri::AppHandle<Wry>, notice: Notice) -> Result<(),
             }).map_err(|e| e.to_string()).unwrap();
         });
     }
-    Ok(())
+}
+
+/// Apply the relevant advisory's cooldown (or [AdvisoryMemory::silenced_notices]) to a notice.
+/// Returns the notice to publish (if any), and whether it's a repeat. A repeat is collapsed into an earlier notice for
+/// the same advisory and user, so no notice is returned. If the earlier notice is no longer in the notice list,
+/// the notice is returned with alerts turned off.
+fn apply_cooldown(app: tauri::AppHandle<Wry>, notice: Notice) -> Result<(Option<Notice>, bool), String> {
+    let cooldown = {
+        let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+        let mut advisory_memory = advisory_memory.lock();
+        check_cooldown(&mut advisory_memory, notice, chrono::Utc::now().timestamp())
+    };
+    match cooldown {
+        Cooldown::Publish(notice) => Ok((Some(notice), false)),
+        Cooldown::Collapsed(existing) => {
+            println!(
+                "Collapsed repeat notice for advisory {:?} and user {:?} (repeat #{})",
+                existing.relevant_advisory_id, existing.relevant_user_id, existing.repeat_count
+            );
+            record_notice_update(&app, &existing);
+            app.emit("vrcmrd:notice_updated", existing)
+                .map_err(|e| e.to_string())?;
+            Ok((None, true))
+        }
+        Cooldown::Muted(notice) => Ok((Some(notice), true)),
+    }
+}
+
+/// What [check_cooldown] decided for a notice.
+#[derive(Debug, PartialEq)]
+enum Cooldown {
+    /// Not a repeat, so it's published as it is.
+    Publish(Notice),
+    /// A repeat, collapsed into this earlier notice.
+    Collapsed(Notice),
+    /// A repeat of a notice that's no longer in the notice list, published without alerts.
+    Muted(Notice),
+}
+
+/// The part of [apply_cooldown] that only needs the advisory memory. `now` is a Unix timestamp.
+fn check_cooldown(advisory_memory: &mut AdvisoryMemory, notice: Notice, now: i64) -> Cooldown {
+    let (Some(advisory_id), Some(user_id)) = (notice.relevant_advisory_id.clone(), notice.relevant_user_id.clone()) else {
+        return Cooldown::Publish(notice);
+    };
+    let cooldown_minutes = advisory_memory
+        .all_advisories
+        .iter()
+        .find(|a| a.id == advisory_id)
+        .and_then(|a| a.cooldown_minutes);
+    let key = (advisory_id, user_id);
+    let in_cooldown = match (cooldown_minutes, advisory_memory.last_notified.get(&key)) {
+        (Some(cooldown_minutes), Some(last)) => now - last < cooldown_minutes as i64 * 60,
+        _ => false,
+    };
+    // A handled notice keeps collapsing repeats after its cooldown, if acknowledging cancels alerts
+    let silenced = advisory_memory.silenced_notices.contains(&key);
+    if !in_cooldown && !silenced {
+        advisory_memory.last_notified.insert(key, now);
+        return Cooldown::Publish(notice);
+    }
+    if let Some(index) = advisory_memory.notices.iter().rposition(|n| {
+        n.relevant_advisory_id == notice.relevant_advisory_id && n.relevant_user_id == notice.relevant_user_id
+    }) {
+        let revision = advisory_memory.next_notice_sequence();
+        let existing = &mut advisory_memory.notices[index];
+        existing.revision = revision;
+        existing.repeat_count += 1;
+        existing.last_repeated_at = notice.created_at.clone();
+        return Cooldown::Collapsed(existing.clone());
+    }
+    Cooldown::Muted(Notice {
+        send_tts: false,
+        send_notification: false,
+        ..notice
+    })
+}
+
+/// Mark a notice as seen.
+/// `by` defaults to the configured moderator name. Claimed or resolved notices are left as they are.
+#[tauri::command]
+pub async fn acknowledge_notice(app: tauri::AppHandle<Wry>, id: u64, by: Option<String>) -> Result<Notice, String> {
+    let by = moderator_name(app.clone(), by).await;
+    change_notice_state(app, id, |state| match state {
+        NoticeState::New => Ok(Some(NoticeState::Acknowledged {
+            by,
+            at: chrono::Utc::now().to_rfc3339(),
+        })),
+        _ => Ok(None),
+    })
+    .await
+}
+
+/// Take a notice, so other moderators know it's being handled.
+/// `by` defaults to the configured moderator name; one of them is required.
+/// Fails if someone else already claimed the notice, or it was resolved.
+#[tauri::command]
+pub async fn claim_notice(app: tauri::AppHandle<Wry>, id: u64, by: Option<String>) -> Result<Notice, String> {
+    let by = moderator_name(app.clone(), by)
+        .await
+        .ok_or("Set your moderator name in preferences to claim notices")?;
+    change_notice_state(app, id, |state| match state {
+        NoticeState::Claimed { by: claimed_by, .. } if *claimed_by == by => Ok(None),
+        NoticeState::Claimed { by: claimed_by, .. } => Err(format!("Already claimed by {}", claimed_by)),
+        NoticeState::Resolved { .. } => Err("This notice was already resolved".to_string()),
+        _ => Ok(Some(NoticeState::Claimed {
+            by,
+            at: chrono::Utc::now().to_rfc3339(),
+        })),
+    })
+    .await
+}
+
+/// Close a notice with a note on what was done. Resolving an already resolved notice replaces the note.
+#[tauri::command]
+pub async fn resolve_notice(
+    app: tauri::AppHandle<Wry>,
+    id: u64,
+    by: Option<String>,
+    note: String,
+) -> Result<Notice, String> {
+    let by = moderator_name(app.clone(), by).await;
+    change_notice_state(app, id, |_| {
+        Ok(Some(NoticeState::Resolved {
+            by,
+            note: note.trim().to_string(),
+            at: chrono::Utc::now().to_rfc3339(),
+        }))
+    })
+    .await
+}
+
+/// Put a notice back to new, i.e. if it was acknowledged by mistake. Repeats alert again.
+#[tauri::command]
+pub async fn reopen_notice(app: tauri::AppHandle<Wry>, id: u64) -> Result<Notice, String> {
+    change_notice_state(app, id, |state| match state {
+        NoticeState::New => Ok(None),
+        _ => Ok(Some(NoticeState::New)),
+    })
+    .await
+}
+
+/// Apply a state change to one of the current instance's notices, bump its revision, record it in History, and emit it.
+/// `change` returns `None` to leave the notice as it is.
+async fn change_notice_state(
+    app: tauri::AppHandle<Wry>,
+    id: u64,
+    change: impl FnOnce(&NoticeState) -> Result<Option<NoticeState>, String>,
+) -> Result<Notice, String> {
+    let notice = {
+        let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+        let mut advisory_memory = advisory_memory.lock();
+        let index = advisory_memory
+            .notices
+            .iter()
+            .position(|n| n.id == id)
+            .ok_or(format!("Notice {} isn't in the current instance's notices", id))?;
+        let Some(state) = change(&advisory_memory.notices[index].state)? else {
+            return Ok(advisory_memory.notices[index].clone());
+        };
+        let revision = advisory_memory.next_notice_sequence();
+        let notice = &mut advisory_memory.notices[index];
+        notice.state = state;
+        notice.revision = revision;
+        let notice = notice.clone();
+        let key = notice.relevant_advisory_id.clone().zip(notice.relevant_user_id.clone());
+        if let Some(key) = key.filter(|_| notice.state == NoticeState::New) {
+            advisory_memory.silenced_notices.remove(&key);
+        }
+        notice
+    };
+    record_notice_update(&app, &notice);
+    if notice.state != NoticeState::New && cancels_alerts(app.clone()).await {
+        cancel_alerts(&app, &notice);
+    }
+    app.emit("vrcmrd:notice_updated", notice.clone())
+        .map_err(|e| e.to_string())?;
+    app.emit("vrcmrd:notice_state_changed", notice.clone())
+        .map_err(|e| e.to_string())?;
+    Ok(notice)
+}
+
+/// Stop alerting for a handled notice: repeats are collapsed into it from now on.
+/// Its TTS is skipped if it hasn't been queued yet, since alerts check [is_handled] first. Speech that's already
+/// queued or playing isn't cut off, because the TTS queue is shared with other notices.
+fn cancel_alerts(app: &tauri::AppHandle<Wry>, notice: &Notice) {
+    if let Some(key) = notice.relevant_advisory_id.clone().zip(notice.relevant_user_id.clone()) {
+        let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+        advisory_memory.lock().silenced_notices.insert(key);
+    }
+}
+
+/// Whether a notice in the current instance is no longer new.
+fn is_handled(app: &tauri::AppHandle<Wry>, id: u64) -> bool {
+    let advisory_memory = app.state::<Mutex<AdvisoryMemory>>();
+    let advisory_memory = advisory_memory.lock();
+    advisory_memory
+        .notices
+        .iter()
+        .any(|n| n.id == id && n.state != NoticeState::New)
+}
+
+async fn cancels_alerts(app: tauri::AppHandle<Wry>) -> bool {
+    get_config(app, ACKNOWLEDGE_CANCELS_ALERTS_CONFIG_KEY.to_string())
+        .await
+        .ok()
+        .flatten()
+        .is_some_and(|v| v == "1")
+}
+
+/// `by` if given, otherwise the configured moderator name.
+async fn moderator_name(app: tauri::AppHandle<Wry>, by: Option<String>) -> Option<String> {
+    let by = match by {
+        Some(by) => Some(by),
+        None => get_config(app, MODERATOR_NAME_CONFIG_KEY.to_string()).await.ok().flatten(),
+    };
+    by.map(|by| by.trim().to_string()).filter(|by| !by.is_empty())
 }
 
 fn wrapped_lines_count(s: &str) -> usize {
@@ -235,4 +694,138 @@ pub fn set_aumid(id: &str) -> windows::core::Result<()> {
     unsafe {
         SetCurrentProcessExplicitAppUserModelID(PCWSTR(wide.as_ptr()))
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::advisories::Advisory;
+
+    fn advisory(id: &str, cooldown_minutes: Option<u32>) -> Advisory {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": id,
+            "level": 2,
+            "message_template": "",
+            "condition": { "type": "Is", "data": "usr_a" },
+            "tags": [],
+            "active": true,
+            "created_at": "",
+            "updated_at": "",
+            "send_notification": true,
+            "send_tts": true,
+            "cooldown_minutes": cooldown_minutes,
+        }))
+        .unwrap()
+    }
+
+    fn notice(advisory_id: &str) -> Notice {
+        Notice {
+            relevant_advisory_id: Some(advisory_id.to_string()),
+            relevant_user_id: Some("usr_a".to_string()),
+            send_tts: true,
+            send_notification: true,
+            ..Default::default()
+        }
+    }
+
+    /// Publish a notice as [publish_notice] would, without alerting.
+    fn publish(advisory_memory: &mut AdvisoryMemory, notice: Notice) -> u64 {
+        let id = advisory_memory.next_notice_sequence();
+        advisory_memory.notices.push(Notice { id, revision: id, ..notice });
+        id
+    }
+
+    fn memory_with(advisories: Vec<Advisory>) -> AdvisoryMemory {
+        let mut advisory_memory = AdvisoryMemory::new();
+        advisory_memory.set(advisories);
+        advisory_memory
+    }
+
+    #[test]
+    fn repeats_within_cooldown_are_collapsed() {
+        let mut advisory_memory = memory_with(vec![advisory("a", Some(10))]);
+        let Cooldown::Publish(first) = check_cooldown(&mut advisory_memory, notice("a"), 0) else {
+            panic!("The first notice should be published");
+        };
+        let id = publish(&mut advisory_memory, first);
+        let Cooldown::Collapsed(existing) = check_cooldown(&mut advisory_memory, notice("a"), 5 * 60) else {
+            panic!("A repeat within the cooldown should be collapsed");
+        };
+        assert_eq!(existing.id, id);
+        assert_eq!(existing.repeat_count, 1);
+        assert!(existing.revision > id);
+        assert_eq!(advisory_memory.notices.len(), 1);
+        // The cooldown runs from the last alert, not the last repeat
+        assert!(matches!(check_cooldown(&mut advisory_memory, notice("a"), 10 * 60), Cooldown::Publish(_)));
+    }
+
+    #[test]
+    fn no_cooldown_always_publishes() {
+        let mut advisory_memory = memory_with(vec![advisory("a", None)]);
+        let first = check_cooldown(&mut advisory_memory, notice("a"), 0);
+        assert_eq!(first, Cooldown::Publish(notice("a")));
+        publish(&mut advisory_memory, notice("a"));
+        assert_eq!(check_cooldown(&mut advisory_memory, notice("a"), 1), Cooldown::Publish(notice("a")));
+        // Notices without an advisory aren't affected either
+        let unrelated = Notice { relevant_advisory_id: None, ..notice("a") };
+        assert_eq!(check_cooldown(&mut advisory_memory, unrelated.clone(), 1), Cooldown::Publish(unrelated));
+    }
+
+    #[test]
+    fn repeats_of_cleared_notices_are_muted() {
+        let mut advisory_memory = memory_with(vec![advisory("a", Some(10))]);
+        check_cooldown(&mut advisory_memory, notice("a"), 0);
+        publish(&mut advisory_memory, notice("a"));
+        advisory_memory.clear_notices();
+        let Cooldown::Muted(muted) = check_cooldown(&mut advisory_memory, notice("a"), 60) else {
+            panic!("A repeat of a cleared notice should be muted");
+        };
+        assert!(!muted.send_tts && !muted.send_notification);
+    }
+
+    #[test]
+    fn silenced_notices_collapse_after_cooldown() {
+        let mut advisory_memory = memory_with(vec![advisory("a", None)]);
+        publish(&mut advisory_memory, notice("a"));
+        advisory_memory.silenced_notices.insert(("a".to_string(), "usr_a".to_string()));
+        assert!(matches!(check_cooldown(&mut advisory_memory, notice("a"), 24 * 60 * 60), Cooldown::Collapsed(_)));
+    }
+
+    #[test]
+    fn notices_since_returns_changes() {
+        let mut advisory_memory = AdvisoryMemory::new();
+        let session = advisory_memory.notice_session.clone();
+        let first = publish(&mut advisory_memory, notice("a"));
+        let second = publish(&mut advisory_memory, notice("b"));
+        let cursor = advisory_memory.notice_sequence;
+        // The first notice changes after the cursor
+        let revision = advisory_memory.next_notice_sequence();
+        advisory_memory.notices[0].revision = revision;
+        let delta = notices_since(&advisory_memory, cursor, &session);
+        assert!(!delta.reset);
+        assert_eq!(delta.notices.iter().map(|n| n.id).collect::<Vec<_>>(), vec![first]);
+        assert_eq!(delta.cursor, revision);
+        let delta = notices_since(&advisory_memory, 0, &session);
+        assert_eq!(delta.notices.iter().map(|n| n.id).collect::<Vec<_>>(), vec![second, first]);
+        assert!(notices_since(&advisory_memory, revision, &session).notices.is_empty());
+    }
+
+    #[test]
+    fn stale_cursors_reset() {
+        let mut advisory_memory = AdvisoryMemory::new();
+        let session = advisory_memory.notice_session.clone();
+        publish(&mut advisory_memory, notice("a"));
+        let cursor = advisory_memory.notice_sequence;
+        // From another session, i.e. before a restart
+        assert!(notices_since(&advisory_memory, cursor, "other").reset);
+        // Ahead of the sequence
+        assert!(notices_since(&advisory_memory, cursor + 1, &session).reset);
+        // From before the notice list was cleared
+        advisory_memory.clear_notices();
+        publish(&mut advisory_memory, notice("b"));
+        let delta = notices_since(&advisory_memory, cursor, &session);
+        assert!(delta.reset);
+        assert_eq!(delta.notices.len(), 1);
+        assert_eq!(delta.session, session);
+    }
+}
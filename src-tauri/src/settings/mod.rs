@@ -36,6 +36,23 @@ pub async fn update_config<R: Runtime>(
     Ok(())
 }
 
+/// Remove a key from the config store, i.e. to reset a stored payload.
+pub async fn remove_config<R: Runtime>(app: tauri::AppHandle<R>, key: String) -> Result<(), String> {
+    match app.store("vrcmrd-config.json") {
+        Ok(store) => {
+            store.delete(&key);
+            store.save().map_err(|e| {
+                eprintln!("Failed to save store: {}", e);
+                e.to_string()
+            })
+        }
+        Err(e) => {
+            eprintln!("Failed to access store: {}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
 #[tauri::command]
 pub async fn get_config<R: Runtime>(
     app: tauri::AppHandle<R>,
@@ -38,12 +38,23 @@ pub struct ActiveAdvisory {
     /// The group ID relevant to this advisory, if any. This allows the group to be linked to
     /// in the UI.
     pub relevant_group_id: Option<String>,
+    /// How the advisory's condition was evaluated when it last matched. `None` if it was kept
+    /// without being re-evaluated.
+    #[serde(default)]
+    pub trace: Option<ConditionTrace>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ts_rs::TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct Notice {
+    /// A monotonic ID, assigned when the notice is published. Later notices always have higher IDs.
+    #[serde(default)]
+    pub id: u64,
+    /// Bumped whenever the notice changes (i.e. when it repeats), from the same sequence as [Self::id].
+    /// Clients that already have the notice can fetch only changes with [crate::notices::get_notices_since].
+    #[serde(default)]
+    pub revision: u64,
     /// The title of the notice. Usually "username joined" or "username changed avatars".
     pub title: Option<String>,
     pub message: String,
@@ -58,11 +69,38 @@ pub struct Notice {
     pub send_notification: bool,
     /// Timestamp of when the notice was created, in RFC 3339 format.
     pub created_at: Option<String>,
+    /// How many more times this notice was raised for the same user during the advisory's
+    /// cooldown (i.e. by rejoining). Repeats are collapsed into this notice instead of alerting again.
+    #[serde(default)]
+    pub repeat_count: u32,
+    /// Timestamp of the most recent repeat, in RFC 3339 format.
+    #[serde(default)]
+    pub last_repeated_at: Option<String>,
+    /// Where moderators are with this notice. Changed with the commands in [crate::notices].
+    #[serde(default)]
+    pub state: NoticeState,
+}
+
+/// The moderation state of a notice. Timestamps are in RFC 3339 format.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ts_rs::TS)]
+#[serde(tag = "type", content = "data")]
+#[ts(export)]
+pub enum NoticeState {
+    #[default]
+    New,
+    /// Someone has seen the notice. `by` is unset if no moderator name is configured.
+    Acknowledged { by: Option<String>, at: String },
+    /// A moderator is handling the notice, so others don't have to.
+    Claimed { by: String, at: String },
+    /// The notice was dealt with, i.e. the user was kicked, or it was a false positive.
+    Resolved { by: Option<String>, note: String, at: String },
 }
 
 impl Default for Notice {
     fn default() -> Self {
         Self {
+            id: 0,
+            revision: 0,
             title: None,
             message: String::new(),
             level: AdvisoryLevel::None,
@@ -73,6 +111,9 @@ impl Default for Notice {
             send_tts: false,
             send_notification: false,
             created_at: None,
+            repeat_count: 0,
+            last_repeated_at: None,
+            state: NoticeState::New,
         }
     }
 }
@@ -87,16 +128,19 @@ pub struct Advisory {
     pub level: AdvisoryLevel,
     /// The message template for this advisory, used to generate notices and when applying
     /// the advisory to a user.
-    /// String substitution may be applied by the matching condition(s).
-    /// Known variables:
-    /// - `{{:username:}}`: the user's display name.
-    /// - `{{:account_age_days:}}`: the user's account age in days (with [AdvisoryCondition::AccountAgeAtMostDays]).
-    /// - `{{:group_name:}}`: the name of the relevant group (with [AdvisoryCondition::IsGroupMember]). Some groups
+    /// See [crate::advisories::templating::KNOWN_TEMPLATE_VARIABLES] for the available variables.
+    /// Some are filled for every user (i.e. `{{:username:}}`, `{{:trust_rank:}}`, `{{:instance_name:}}`),
+    /// and some only by the matching condition(s) (i.e. `{{:group_name:}}`). Some groups
     /// frequently change names to avoid identification. You may want to keep the original group name in the advisory.
     ///
     /// Known patterns:
-    /// - `{{:variable_name:}}`: the value of `variable_name`. TODO: what if it isn't defined?
+    /// - `{{:variable_name:}}`: the value of `variable_name`, or nothing if it isn't defined.
     /// - `{{:variable_name||default_value:}}`: the value of `variable_name`, or `default_value` if it isn't defined.
+    /// - `{{:variable_name|upper:}}`, `{{:variable_name|truncate:20:}}`, `{{:join_time|relative_time:}}`: filters, applied in order.
+    /// - `{{:#if variable_name:}}...{{:else:}}...{{:/if:}}`: only include text if `variable_name` is set and not empty.
+    ///   Use `{{:#if !variable_name:}}` to negate.
+    ///
+    /// Templates are validated when the advisory is saved; unknown variables are rejected.
     pub message_template: String,
     /// The conditions under which this advisory applies to a user.
     /// To apply multiple conditions, use [AdvisoryCondition::AllOf] or [AdvisoryCondition::AnyOf].
@@ -127,8 +171,84 @@ pub struct Advisory {
     pub send_notification: bool,
     /// Whether to speak the advisory message via TTS when this advisory matches.
     pub send_tts: bool,
+    /// If set, repeat notices for the same user (i.e. when they rejoin) within this many minutes
+    /// don't alert again. They're collapsed into the first notice in History instead.
+    #[serde(default)]
+    pub cooldown_minutes: Option<u32>,
+    /// When this advisory stops applying, in RFC 3339 format. Once it passes, the advisory is
+    /// deactivated automatically. Useful for short-term watches ("keep an eye on them for a week").
+    #[serde(default)]
+    pub expires_at: Option<String>,
+    /// Actions to run when this advisory newly matches a user, in addition to the notice.
+    /// Each action runs independently; one failing doesn't stop the others.
+    #[serde(default)]
+    pub actions: Vec<AdvisoryAction>,
+    /// How much this advisory adds to a user's risk score when it matches.
+    /// If unset, the weight is derived from the level; see [Advisory::effective_weight].
+    #[serde(default)]
+    pub weight: Option<u32>,
+}
+
+impl Advisory {
+    /// The advisory's weight towards a user's risk score. Defaults to doubling with each level,
+    /// so two Medium advisories weigh as much as one High.
+    pub fn effective_weight(&self) -> u32 {
+        self.weight.unwrap_or(match self.level {
+            AdvisoryLevel::None => 0,
+            AdvisoryLevel::Low => 1,
+            AdvisoryLevel::Medium => 2,
+            AdvisoryLevel::High => 4,
+            AdvisoryLevel::Maximum => 8,
+        })
+    }
+    /// Whether [Advisory::expires_at] has passed.
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .as_deref()
+            .is_some_and(|expires_at| is_past(expires_at))
+    }
 }
 
+/// Whether the given RFC 3339 timestamp is in the past.
+/// Timestamps that can't be parsed are treated as never passing.
+pub fn is_past(timestamp: &str) -> bool {
+    match chrono::DateTime::parse_from_rfc3339(timestamp) {
+        Ok(timestamp) => timestamp.with_timezone(&chrono::Utc) <= chrono::Utc::now(),
+        Err(e) => {
+            eprintln!("Invalid expiry timestamp '{}': {}", timestamp, e);
+            false
+        }
+    }
+}
+
+/// An action run when an advisory newly matches a user.
+/// Templates in actions use the same variables as [Advisory::message_template].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ts_rs::TS)]
+#[serde(tag = "type", content = "data")] // Best for TypeScript discriminated unions
+#[ts(export)]
+pub enum AdvisoryAction {
+    /// Add the user to the watchlist with the given name. The watchlist is created if it doesn't exist.
+    /// Use [AdvisoryCondition::InWatchlist] to match users on it.
+    AddToWatchlist(String),
+    /// Copy the user's ID to the clipboard, so it can be pasted into a ban or report form.
+    CopyUserId,
+    /// POST the payload to the given URL as `application/json`. Variables are escaped for use inside
+    /// JSON strings, and the payload isn't sent if it isn't valid JSON after templating.
+    Webhook { url: String, payload_template: String },
+    /// Append a line to an incident log file. The path is relative to the `incident_logs` folder in
+    /// the app's local data directory, and can't leave it.
+    IncidentLog { path: String, line_template: String },
+    /// Send an OSC message with a single string argument over UDP, i.e. to trigger something in-world.
+    /// `target` is the `host:port` to send to, i.e. `127.0.0.1:9000`.
+    Osc { target: String, address: String, argument_template: String },
+}
+
+/// The minimum confidence for [AdvisoryCondition::AvatarMayBe] conditions saved before it could be set.
+/// Matches avatars found by their image file, by name from the same creator, or through a closely-matching bundle file,
+/// but not other avatars by the same creator. Bundle file matches are 80% scaled by how well the bundle's name matches,
+/// so this is below 80% for them to pass without an exact name match.
+pub const DEFAULT_AVATAR_MIN_CONFIDENCE: u8 = 70;
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ts_rs::TS)]
 #[serde(tag = "type", content = "data")] // Best for TypeScript discriminated unions
 #[ts(export)]
@@ -138,6 +258,10 @@ pub enum AdvisoryCondition {
     /// Useful to set advisories for specific users, i.e. to watchlist certain users,
     /// or to exclude users from certain advisories.
     Is(String),
+    /// The user has the given ID (`usr_***`), until `expires_at` (in RFC 3339 format).
+    /// Use this in an [AnyOf] list of user IDs for short-term watches. Expired entries
+    /// stop matching, and are pruned from [AnyOf] lists automatically.
+    IsUntil { id: String, expires_at: String },
     // These should be applied at join-time.
     /// The user is a member of the group with the given ID (`grp_***`).
     /// Useful to set advisories for known bad groups, or for moderators.
@@ -150,12 +274,23 @@ pub enum AdvisoryCondition {
     /// The given condition applies to each group the user is in, and if any group meets the
     /// condition, the user matches.
     GroupCondition(AdvisoryGroupCondition),
+    /// The user is in at least `count` groups that meet the given condition.
+    /// Use an empty [AdvisoryGroupCondition::AllOf] to count every group, and wrap in [Not] for
+    /// "fewer than `count` groups", i.e. an old account that isn't in any groups.
+    /// Only evaluated once the user's group list is known; until then, the advisory is kept as it is
+    /// (even under [Not]).
+    GroupCountAtLeast { condition: AdvisoryGroupCondition, count: u32 },
     /// The user's trust rank is lower than or equal to the given trust rank.
     /// Useful to set advisories for visitors or nuisances.
     TrustRankAtMost(TrustRank),
     /// The user's account age is less than or equal to the given number of days.
     /// Useful to set advisories for new accounts.
     AccountAgeAtMostDays(u32),
+    /// The user has the given raw VRChat tag (i.e. `system_probable_troll`, `language_jpn`).
+    /// Unlike [TrustRankAtMost], this isn't hidden by the user's trust rank.
+    HasTag(String),
+    /// The user has a raw VRChat tag starting with the given prefix (i.e. `language_`).
+    TagPrefix(String),
     //PerfRankAtMost(PerfRank),
     /// The user's display name contains the given substring (case-insensitive).
     /// Useful to set advisories for ban-evaders or VIPs.
@@ -166,9 +301,39 @@ pub enum AdvisoryCondition {
     /// The user's status field contains the given substring (case-insensitive).
     /// Useful to look out for known-bad statuses (i.e. slurs).
     StatusContains(String),
+    /// The user's bio contains the given substring (case-insensitive).
+    /// Bad actors often advertise crasher Discords or slurs in their bio.
+    BioContains(String),
+    /// The user's bio matches the given regular expression (case-insensitive).
+    /// The expression is checked when the advisory is saved.
+    BioMatches(String),
+    /// One of the user's bio links points to the given domain (i.e. `discord.gg`) or one of its subdomains.
+    BioLinkDomainIs(String),
     /// The user does not have their age verified. If you're running an un-gated 18+ instance,
     /// this will let you know who you have to manually verify.
     AgeNotVerified,
+    /// The user was kicked from an instance we were in within the given number of hours.
+    /// Kicks are kept by user ID, so this still applies if they rejoin right away.
+    /// Only kicks within the moderation history window (`moderation_history_hours`) are remembered.
+    RecentlyKickedWithinHours(u32),
+    /// The user was banned from an instance we were in within the given number of hours.
+    RecentlyBannedWithinHours(u32),
+    /// The user has been in the instance for at least the given number of minutes.
+    /// Advisories are re-evaluated periodically (see `reevaluation_interval_seconds`), so this can start
+    /// matching mid-session, i.e. "in the instance for 30 minutes without age verification".
+    TimeInInstanceAtLeastMinutes(u32),
+    /// The logged-in account is friends with the user.
+    IsFriend,
+    /// The user is on the named watchlist, i.e. added by [AdvisoryAction::AddToWatchlist].
+    InWatchlist(String),
+    /// The user is on the host's list of moderator-trusted users (i.e. friends of staff).
+    /// Useful to write advisories like "new account AND not trusted by staff".
+    IsStaffTrusted,
+    /// The user holds the given role (`grol_***`) in the given group (`grp_***`).
+    /// Roles are only fetched for the group hosting the current instance, so this only matches in that group's instances.
+    /// An empty `group_id` means the hosting group, and an empty `role_id` means any management (staff) role.
+    /// Wrap in [Not] to exempt group staff from an advisory.
+    HasGroupRole { group_id: String, role_id: String },
     /// The user is on the given platform (e.g., "standalonewindows", "android", "ios").
     /// This matches the platform string.
     /// We cannot currently determine whether the user is in VR or not. It's likely that the
@@ -181,13 +346,24 @@ pub enum AdvisoryCondition {
     /// Useful to set advisories for known crasher avatars or avatars that have strong effects.
     /// **Note:** we can't guarantee that the user is using this avatar, only that it
     /// is one that may be used based on the limited information available.
-    AvatarMayBe(String),
+    /// This matches the avatar found by avatar search, other avatars by the same creator, and avatars
+    /// found through observed bundle files, each with a confidence from 0 to 100. Only avatars found with at least
+    /// `min_confidence` match; the confidence is available in the `avatar_confidence` variable.
+    AvatarMayBe { avatar_id: String, min_confidence: u8 },
     // TODO: PerfRankAtMost(AvatarPerformance),
     /// The user's current avatar's name contains the given substring (case-insensitive).
     AvatarNameContains(String),
     /// The user's current avatar is created by the given user ID (`usr_***`).
     /// Useful to set advisories for creators of several bad avatars.
     AvatarCreatorIs(String),
+    /// The user's current avatar is private, according to avatar search.
+    /// Only known once the avatar has been found, so this doesn't match avatars that couldn't be looked up.
+    AvatarIsPrivate,
+    /// The user's current avatar is public, according to avatar search.
+    AvatarIsPublic,
+    /// One of the user's avatar images is the given file (`file_***`).
+    /// Useful to match known crasher avatars even when the avatar ID can't be found.
+    AvatarImageFileIs(String),
 
     // == Instance conditions ==
     // ** NOTE: these only generate notices and don't themselves apply to users! **
@@ -265,43 +441,352 @@ impl AdvisoryCondition {
     where
         F: Fn(AdvisoryCondition) -> bool,
     {
+        self.evaluate_with(&|condition| ConditionResult::from(evaluator(condition)), false)
+            .matched
+    }
+
+    /// Like [Self::evaluate], but returns a trace of how each sub-condition was evaluated,
+    /// to explain why an advisory did or didn't match.
+    /// Sub-conditions of [AnyOf] and [AllOf] after the one that decided the result are not evaluated,
+    /// and are marked as skipped.
+    ///
+    /// Results marked [ConditionResult::kept_existing] are unknown: [Not] doesn't flip them, and [AnyOf]
+    /// and [AllOf] are only unknown if no known sub-condition decides them. An unknown result keeps
+    /// the advisory as it is, so missing data never makes a negated condition match.
+    pub fn evaluate_traced<F>(&self, evaluator: &F) -> ConditionTrace
+    where
+        F: Fn(AdvisoryCondition) -> ConditionResult,
+    {
+        self.evaluate_with(evaluator, true)
+    }
+
+    /// Evaluate the condition. Unless `traced` is set, only the result is filled in, without describing
+    /// each condition or keeping the sub-condition traces.
+    fn evaluate_with<F>(&self, evaluator: &F, traced: bool) -> ConditionTrace
+    where
+        F: Fn(AdvisoryCondition) -> ConditionResult,
+    {
+        let node = |matched| if traced { ConditionTrace::new(self, matched) } else { ConditionTrace::bare(matched) };
         match self {
             AdvisoryCondition::Not { data } => {
                 if let AdvisoryCondition::Not { data: e } = &**data {
-                    // double negation
                     if let AdvisoryCondition::Not { data: _ } = &**e {
                         // triple negation!
                         eprintln!(
                             "Recursion in advisory condition is not allowed. Returning false"
                         );
-                        return false;
+                        return node(false);
                     }
-                    return e.evaluate(evaluator);
                 }
-                !data.evaluate(evaluator)
+                // Double negation works out the same as evaluating the inner condition
+                let child = data.evaluate_with(evaluator, traced);
+                // An unknown result stays unknown, keeping the existing advisory, rather than flipping
+                let matched = if child.kept_existing { child.matched } else { !child.matched };
+                let mut trace = node(matched);
+                trace.kept_existing = child.kept_existing;
+                if traced {
+                    trace.children.push(child);
+                }
+                trace
             }
-            AdvisoryCondition::AnyOf(conditions) => {
+            AdvisoryCondition::AnyOf(conditions) | AdvisoryCondition::AllOf(conditions) => {
+                let any = matches!(self, AdvisoryCondition::AnyOf(_));
+                // An empty AnyOf matches nothing, and an empty AllOf matches everything
+                let mut trace = node(!any);
+                let mut decided = false;
+                // Unknown sub-conditions can't decide the result, but a known one still can
+                let mut unknown = None;
                 for condition in conditions {
-                    if condition.evaluate(evaluator) {
-                        return true;
+                    if decided {
+                        if !traced {
+                            break;
+                        }
+                        trace.children.push(ConditionTrace::skipped(condition));
+                        continue;
                     }
+                    let child = condition.evaluate_with(evaluator, traced);
+                    if child.kept_existing {
+                        unknown.get_or_insert(child.matched);
+                    } else if child.matched == any {
+                        trace.matched = any;
+                        decided = true;
+                    }
+                    if traced {
+                        trace.children.push(child);
+                    }
+                }
+                if let (false, Some(existing)) = (decided, unknown) {
+                    trace.matched = existing;
+                    trace.kept_existing = true;
                 }
-                false
+                trace
+            }
+            AdvisoryCondition::None => panic!("AdvisoryCondition::None should never be evaluated"),
+            _ => {
+                let result = evaluator(self.clone());
+                let mut trace = node(result.matched);
+                trace.value = result.value;
+                trace.kept_existing = result.kept_existing;
+                trace
             }
-            AdvisoryCondition::AllOf(conditions) => {
+        }
+    }
+
+    /// Every group condition in this condition, including nested ones.
+    pub fn group_conditions(&self) -> Vec<&AdvisoryGroupCondition> {
+        match self {
+            AdvisoryCondition::Not { data } => data.group_conditions(),
+            AdvisoryCondition::AnyOf(conditions) | AdvisoryCondition::AllOf(conditions) => {
+                conditions.iter().flat_map(|c| c.group_conditions()).collect()
+            }
+            AdvisoryCondition::GroupCondition(condition) => vec![condition],
+            AdvisoryCondition::GroupCountAtLeast { condition, .. } => vec![condition],
+            _ => Vec::new(),
+        }
+    }
+
+    /// The data this condition needs to be evaluated, including nested conditions.
+    pub fn data_requirements(&self) -> DataRequirements {
+        let mut requirements = DataRequirements::default();
+        match self {
+            AdvisoryCondition::Not { data } => requirements = data.data_requirements(),
+            AdvisoryCondition::AnyOf(conditions) | AdvisoryCondition::AllOf(conditions) => {
                 for condition in conditions {
-                    if !condition.evaluate(evaluator) {
-                        return false;
-                    }
+                    requirements.merge(condition.data_requirements());
                 }
-                true
             }
-            AdvisoryCondition::None => panic!("AdvisoryCondition::None should never be evaluated"),
-            _ => evaluator(self.clone()),
+            AdvisoryCondition::IsGroupMember(_) | AdvisoryCondition::InGroupNameContains(_) => requirements.groups = true,
+            AdvisoryCondition::GroupCondition(condition) | AdvisoryCondition::GroupCountAtLeast { condition, .. } => {
+                requirements.groups = true;
+                requirements.group_details = condition.needs_details();
+            }
+            AdvisoryCondition::AvatarMayBe { .. }
+            | AdvisoryCondition::AvatarNameContains(_)
+            | AdvisoryCondition::AvatarCreatorIs(_)
+            | AdvisoryCondition::AvatarIsPrivate
+            | AdvisoryCondition::AvatarIsPublic
+            | AdvisoryCondition::AvatarImageFileIs(_) => requirements.avatar = true,
+            _ => {}
+        }
+        requirements
+    }
+
+    /// The condition's type, i.e. `UsernameContains`.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            AdvisoryCondition::Is(_) => "Is",
+            AdvisoryCondition::IsUntil { .. } => "IsUntil",
+            AdvisoryCondition::IsGroupMember(_) => "IsGroupMember",
+            AdvisoryCondition::InGroupNameContains(_) => "InGroupNameContains",
+            AdvisoryCondition::GroupCondition(_) => "GroupCondition",
+            AdvisoryCondition::GroupCountAtLeast { .. } => "GroupCountAtLeast",
+            AdvisoryCondition::TrustRankAtMost(_) => "TrustRankAtMost",
+            AdvisoryCondition::AccountAgeAtMostDays(_) => "AccountAgeAtMostDays",
+            AdvisoryCondition::HasTag(_) => "HasTag",
+            AdvisoryCondition::TagPrefix(_) => "TagPrefix",
+            AdvisoryCondition::UsernameContains(_) => "UsernameContains",
+            AdvisoryCondition::PronounContains(_) => "PronounContains",
+            AdvisoryCondition::StatusContains(_) => "StatusContains",
+            AdvisoryCondition::BioContains(_) => "BioContains",
+            AdvisoryCondition::BioMatches(_) => "BioMatches",
+            AdvisoryCondition::BioLinkDomainIs(_) => "BioLinkDomainIs",
+            AdvisoryCondition::AgeNotVerified => "AgeNotVerified",
+            AdvisoryCondition::RecentlyKickedWithinHours(_) => "RecentlyKickedWithinHours",
+            AdvisoryCondition::RecentlyBannedWithinHours(_) => "RecentlyBannedWithinHours",
+            AdvisoryCondition::TimeInInstanceAtLeastMinutes(_) => "TimeInInstanceAtLeastMinutes",
+            AdvisoryCondition::IsFriend => "IsFriend",
+            AdvisoryCondition::InWatchlist(_) => "InWatchlist",
+            AdvisoryCondition::IsStaffTrusted => "IsStaffTrusted",
+            AdvisoryCondition::HasGroupRole { .. } => "HasGroupRole",
+            AdvisoryCondition::PlatformIs(_) => "PlatformIs",
+            AdvisoryCondition::AvatarMayBe { .. } => "AvatarMayBe",
+            AdvisoryCondition::AvatarNameContains(_) => "AvatarNameContains",
+            AdvisoryCondition::AvatarCreatorIs(_) => "AvatarCreatorIs",
+            AdvisoryCondition::AvatarIsPrivate => "AvatarIsPrivate",
+            AdvisoryCondition::AvatarIsPublic => "AvatarIsPublic",
+            AdvisoryCondition::AvatarImageFileIs(_) => "AvatarImageFileIs",
+            AdvisoryCondition::LogLinePrefix(_) => "LogLinePrefix",
+            AdvisoryCondition::InstanceOwner(_) => "InstanceOwner",
+            AdvisoryCondition::InstanceGroupRestricted(_) => "InstanceGroupRestricted",
+            AdvisoryCondition::Not { .. } => "Not",
+            AdvisoryCondition::AnyOf(_) => "AnyOf",
+            AdvisoryCondition::AllOf(_) => "AllOf",
+            AdvisoryCondition::None => "None",
+        }
+    }
+
+    /// The condition's parameter as text, for non-meta conditions that have one.
+    fn parameter(&self) -> Option<String> {
+        match self {
+            AdvisoryCondition::Is(text)
+            | AdvisoryCondition::IsGroupMember(text)
+            | AdvisoryCondition::InGroupNameContains(text)
+            | AdvisoryCondition::HasTag(text)
+            | AdvisoryCondition::TagPrefix(text)
+            | AdvisoryCondition::UsernameContains(text)
+            | AdvisoryCondition::PronounContains(text)
+            | AdvisoryCondition::StatusContains(text)
+            | AdvisoryCondition::BioContains(text)
+            | AdvisoryCondition::BioMatches(text)
+            | AdvisoryCondition::BioLinkDomainIs(text)
+            | AdvisoryCondition::InWatchlist(text)
+            | AdvisoryCondition::PlatformIs(text)
+            | AdvisoryCondition::AvatarNameContains(text)
+            | AdvisoryCondition::AvatarCreatorIs(text)
+            | AdvisoryCondition::AvatarImageFileIs(text)
+            | AdvisoryCondition::LogLinePrefix(text)
+            | AdvisoryCondition::InstanceOwner(text) => Some(text.clone()),
+            AdvisoryCondition::IsUntil { id, expires_at } => Some(format!("{} until {}", id, expires_at)),
+            // Group conditions don't contain advisory conditions, so this only serializes a small tree
+            AdvisoryCondition::GroupCondition(condition) => serde_json::to_string(condition).ok(),
+            AdvisoryCondition::GroupCountAtLeast { condition, count } => serde_json::to_string(condition)
+                .ok()
+                .map(|condition| format!("{} × {}", count, condition)),
+            AdvisoryCondition::TrustRankAtMost(trust_rank) => Some(format!("{:?}", trust_rank)),
+            AdvisoryCondition::AccountAgeAtMostDays(number)
+            | AdvisoryCondition::RecentlyKickedWithinHours(number)
+            | AdvisoryCondition::RecentlyBannedWithinHours(number)
+            | AdvisoryCondition::TimeInInstanceAtLeastMinutes(number) => Some(number.to_string()),
+            AdvisoryCondition::HasGroupRole { group_id, role_id } => Some(format!(
+                "{} in {}",
+                if role_id.is_empty() { "any staff role" } else { role_id },
+                if group_id.is_empty() { "the hosting group" } else { group_id }
+            )),
+            AdvisoryCondition::AvatarMayBe { avatar_id, min_confidence } => {
+                Some(format!("{} (at least {}%)", avatar_id, min_confidence))
+            }
+            AdvisoryCondition::InstanceGroupRestricted(group_ids) => group_ids.as_ref().map(|ids| ids.join(", ")),
+            AdvisoryCondition::AgeNotVerified
+            | AdvisoryCondition::IsFriend
+            | AdvisoryCondition::IsStaffTrusted
+            | AdvisoryCondition::AvatarIsPrivate
+            | AdvisoryCondition::AvatarIsPublic
+            | AdvisoryCondition::Not { .. }
+            | AdvisoryCondition::AnyOf(_)
+            | AdvisoryCondition::AllOf(_)
+            | AdvisoryCondition::None => None,
+        }
+    }
+}
+
+/// Which data sources active advisories need, so only the needed data is fetched for each user.
+/// See [AdvisoryCondition::data_requirements]. The profile (with the bio), the instance info and the user's roles in
+/// the group hosting the instance aren't listed, since they're always fetched for the user list.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DataRequirements {
+    /// The user's group list.
+    pub groups: bool,
+    /// Details of the user's groups, beyond what the group list includes.
+    pub group_details: bool,
+    /// The user's avatar, from avatar search.
+    pub avatar: bool,
+}
+
+impl DataRequirements {
+    pub fn merge(&mut self, other: DataRequirements) {
+        self.groups |= other.groups;
+        self.group_details |= other.group_details;
+        self.avatar |= other.avatar;
+    }
+}
+
+/// The result of evaluating a single (non-meta) condition, for [AdvisoryCondition::evaluate_traced].
+pub struct ConditionResult {
+    pub matched: bool,
+    /// The user's value that was compared, if there is one.
+    pub value: Option<String>,
+    /// Whether the result came from keeping an already-active advisory, because the data needed wasn't available.
+    pub kept_existing: bool,
+}
+
+impl From<bool> for ConditionResult {
+    fn from(matched: bool) -> Self {
+        Self {
+            matched,
+            value: None,
+            kept_existing: false,
+        }
+    }
+}
+
+/// How a condition was evaluated for a user, so moderators can see why an advisory fired.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ts_rs::TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ConditionTrace {
+    /// The condition's type, i.e. `UsernameContains`.
+    pub condition: String,
+    /// The condition's parameter, i.e. the substring to look for.
+    pub parameter: Option<String>,
+    pub matched: bool,
+    /// The user's value that was compared, if there is one.
+    pub value: Option<String>,
+    /// Whether the result came from keeping an already-active advisory, because the data needed wasn't available.
+    pub kept_existing: bool,
+    /// Whether the condition wasn't evaluated, because an earlier sibling already decided the result.
+    pub skipped: bool,
+    pub children: Vec<ConditionTrace>,
+}
+
+impl ConditionTrace {
+    fn new(condition: &AdvisoryCondition, matched: bool) -> Self {
+        Self {
+            condition: condition.type_name().to_string(),
+            parameter: condition.parameter(),
+            matched,
+            value: None,
+            kept_existing: false,
+            skipped: false,
+            children: Vec::new(),
+        }
+    }
+    /// A trace with only the result, for [AdvisoryCondition::evaluate].
+    fn bare(matched: bool) -> Self {
+        Self {
+            condition: String::new(),
+            parameter: None,
+            matched,
+            value: None,
+            kept_existing: false,
+            skipped: false,
+            children: Vec::new(),
+        }
+    }
+    fn skipped(condition: &AdvisoryCondition) -> Self {
+        Self {
+            skipped: true,
+            ..Self::new(condition, false)
         }
     }
 }
 
+/// How often an advisory has matched, kept across restarts so that stale or noisy advisories can be spotted.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ts_rs::TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct AdvisoryStats {
+    /// How many times the advisory was newly applied to a user.
+    pub total_matches: u32,
+    /// Matches when a user joined (or their user info was first fetched).
+    pub join_leave_matches: u32,
+    /// Matches when a user switched avatars.
+    pub avatar_switched_matches: u32,
+    /// Matches during a periodic re-evaluation, or after the advisories changed.
+    #[serde(default)]
+    pub reevaluation_matches: u32,
+    /// The most recently matched distinct user IDs, oldest first.
+    /// Capped at [crate::advisories::stats::MAX_MATCHED_USER_IDS].
+    pub matched_user_ids: Vec<String>,
+    /// How many distinct users this advisory has matched. Users who dropped off
+    /// [Self::matched_user_ids] are counted again if they match again.
+    #[serde(default)]
+    pub matched_user_count: u32,
+    /// Timestamp of the first match, in RFC 3339 format.
+    pub first_fired_at: Option<String>,
+    /// Timestamp of the most recent match, in RFC 3339 format.
+    pub last_fired_at: Option<String>,
+}
+
 pub fn make_notice(
     advisory: &Advisory,
     active_advisory: &ActiveAdvisory,
@@ -319,5 +804,186 @@ pub fn make_notice(
         send_tts: advisory.send_tts,
         send_notification: advisory.send_notification,
         created_at: Some(chrono::Utc::now().to_rfc3339()),
+        repeat_count: 0,
+        last_repeated_at: None,
+        ..Default::default()
+    }
+}
+
+/// A variable that can be used in advisory templates, for the template editor.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ts_rs::TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TemplateVariable {
+    pub name: String,
+    pub description: String,
+    /// Whether the variable is filled for every user (when the information is known),
+    /// rather than only by specific conditions.
+    pub always_available: bool,
+}
+
+/// A named set of advisories that applies only in matching instances, i.e. one per community you moderate for.
+/// While a profile is active, only its advisories are evaluated. When no profile matches the instance,
+/// every active advisory applies.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ts_rs::TS)]
+#[ts(export)]
+pub struct AdvisoryProfile {
+    pub id: String,
+    pub name: String,
+    /// Advisories in the profile, by ID.
+    pub advisory_ids: Vec<String>,
+    /// Advisories with any of these tags are also in the profile.
+    pub tags: Vec<String>,
+    /// The profile applies to instances matching any of these rules. Profiles are checked in order,
+    /// and the first one with a matching rule is used.
+    pub rules: Vec<ProfileRule>,
+    pub active: bool,
+}
+
+impl AdvisoryProfile {
+    /// Whether the advisory belongs to this profile, by ID or by tag.
+    pub fn includes(&self, advisory: &Advisory) -> bool {
+        self.advisory_ids.contains(&advisory.id) || advisory.tags.iter().any(|tag| self.tags.contains(tag))
+    }
+}
+
+/// Matches instances by their ID. Unset fields match any instance; set fields must all match.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ts_rs::TS)]
+#[ts(export)]
+pub struct ProfileRule {
+    /// The instance owner (`grp_***` or `usr_***`).
+    pub owner: Option<String>,
+    /// The world ID (`wrld_***`).
+    pub world: Option<String>,
+    /// The instance type, as in [crate::types::VrcMrdInstanceId::instance_type], i.e. "group public".
+    pub instance_type: Option<String>,
+    /// The region code, i.e. "us" or "eu".
+    pub region: Option<String>,
+}
+
+impl ProfileRule {
+    pub fn matches(&self, instance: &crate::types::VrcMrdInstanceId) -> bool {
+        fn field_matches(wanted: &Option<String>, actual: Option<&str>) -> bool {
+            match wanted.as_deref().filter(|w| !w.is_empty()) {
+                Some(wanted) => actual.is_some_and(|actual| actual.eq_ignore_ascii_case(wanted)),
+                None => true,
+            }
+        }
+        field_matches(&self.owner, instance.owner.as_deref())
+            && field_matches(&self.world, Some(&instance.world))
+            && field_matches(&self.instance_type, Some(&instance.instance_type))
+            && field_matches(&self.region, Some(&instance.region))
+    }
+}
+
+/// Raises a user's effective level once their risk score (the sum of their matching advisories' weights)
+/// reaches `min_score`, i.e. three Medium advisories (score 6) count as High.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ts_rs::TS)]
+#[ts(export)]
+pub struct EscalationRule {
+    pub id: String,
+    pub name: String,
+    pub min_score: u32,
+    /// The effective level for users who reach `min_score`.
+    pub level: AdvisoryLevel,
+    /// The message of the notice sent when a user is escalated by this rule.
+    /// Supports the same syntax as [Advisory::message_template], with `{{:risk_score:}}` and `{{:advisory_count:}}`.
+    pub message_template: String,
+    pub active: bool,
+    pub send_notification: bool,
+    pub send_tts: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A leaf for each kind of result: `Is("true")`, `Is("false")`, and unknown results that keep an
+    /// advisory that's already active (`Is("kept")`) or keep it inactive (`Is("unknown")`).
+    fn leaf(condition: AdvisoryCondition) -> ConditionResult {
+        let AdvisoryCondition::Is(result) = condition else {
+            panic!("Unexpected condition {:?}", condition);
+        };
+        match result.as_str() {
+            "true" => true.into(),
+            "false" => false.into(),
+            "kept" | "unknown" => ConditionResult {
+                matched: result == "kept",
+                value: None,
+                kept_existing: true,
+            },
+            _ => panic!("Unexpected result {}", result),
+        }
+    }
+
+    fn is(result: &str) -> AdvisoryCondition {
+        AdvisoryCondition::Is(result.to_string())
+    }
+
+    fn not(condition: AdvisoryCondition) -> AdvisoryCondition {
+        AdvisoryCondition::Not { data: Box::new(condition) }
+    }
+
+    /// (matched, kept_existing)
+    fn result(condition: AdvisoryCondition) -> (bool, bool) {
+        let trace = condition.evaluate_traced(&leaf);
+        // The untraced evaluation agrees
+        assert_eq!(condition.evaluate_with(&leaf, false).matched, trace.matched);
+        (trace.matched, trace.kept_existing)
+    }
+
+    #[test]
+    fn not_flips_known_results() {
+        assert_eq!(result(not(is("true"))), (false, false));
+        assert_eq!(result(not(is("false"))), (true, false));
+        // Double negation
+        assert_eq!(result(not(not(is("true")))), (true, false));
+    }
+
+    #[test]
+    fn not_keeps_unknown_results() {
+        assert_eq!(result(not(is("kept"))), (true, true));
+        assert_eq!(result(not(is("unknown"))), (false, true));
+        assert_eq!(result(not(not(is("unknown")))), (false, true));
+    }
+
+    #[test]
+    fn known_results_decide_over_unknown_ones() {
+        assert_eq!(result(AdvisoryCondition::AnyOf(vec![is("unknown"), is("true")])), (true, false));
+        assert_eq!(result(AdvisoryCondition::AllOf(vec![is("kept"), is("false")])), (false, false));
+        assert_eq!(result(AdvisoryCondition::AllOf(vec![not(is("unknown")), is("false")])), (false, false));
+    }
+
+    #[test]
+    fn undecided_results_are_unknown() {
+        assert_eq!(result(AdvisoryCondition::AnyOf(vec![is("kept"), is("false")])), (true, true));
+        assert_eq!(result(AdvisoryCondition::AnyOf(vec![is("unknown"), is("false")])), (false, true));
+        assert_eq!(result(AdvisoryCondition::AllOf(vec![is("true"), is("unknown")])), (false, true));
+        assert_eq!(result(AdvisoryCondition::AllOf(vec![is("true"), is("kept")])), (true, true));
+        // Unknown all the way up, so a negated condition can't match on missing data
+        assert_eq!(result(not(AdvisoryCondition::AllOf(vec![is("true"), is("unknown")]))), (false, true));
+    }
+
+    #[test]
+    fn empty_lists() {
+        assert_eq!(result(AdvisoryCondition::AnyOf(Vec::new())), (false, false));
+        assert_eq!(result(AdvisoryCondition::AllOf(Vec::new())), (true, false));
+    }
+
+    #[test]
+    fn decided_lists_skip_the_rest() {
+        let trace = AdvisoryCondition::AnyOf(vec![is("true"), is("false")]).evaluate_traced(&leaf);
+        assert!(trace.matched);
+        assert_eq!(trace.children.len(), 2);
+        assert!(!trace.children[0].skipped);
+        assert!(trace.children[1].skipped);
+        // Untraced evaluation doesn't keep children
+        assert!(AdvisoryCondition::AnyOf(vec![is("true")]).evaluate_with(&leaf, false).children.is_empty());
+    }
+
+    #[test]
+    fn evaluate_treats_bool_results_as_known() {
+        let condition = not(AdvisoryCondition::AnyOf(vec![is("false"), is("true")]));
+        assert!(!condition.evaluate(&|condition| leaf(condition).matched));
     }
 }
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::types::PartialGroup;
+
 /// Conditions applied to a VRChat Group for an advisory. This way, a set of conditions can be applied to the same group, rather than to any group the user is in.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ts_rs::TS)]
 #[serde(tag = "type", content = "data")] // Best for TypeScript discriminated unions
@@ -15,6 +17,17 @@ pub enum AdvisoryGroupCondition {
     /// This is useful for applying advisories to groups owned by specific users, such as known abusers or trusted community members.
     /// For instance, you can set up an advisory to look out for potential group-staff impersonation, and ignore any groups that are owned by actual staff members.
     OwnerIs(String),
+    /// The group has at most the given number of members. Throwaway groups tend to be tiny.
+    MemberCountAtMost(u32),
+    /// The group was created within the given number of days.
+    /// Needs the group's details, which are only fetched for groups that could match an active advisory.
+    CreatedWithinDays(u32),
+    /// Anyone can join the group, without an invite or a request.
+    /// Needs the group's details, which are only fetched for groups that could match an active advisory.
+    IsOpen,
+    /// The group is verified by VRChat.
+    /// Needs the group's details, which are only fetched for groups that could match an active advisory.
+    IsVerified,
 
     // == Meta-conditions ==
     /// The condition _does not_ match.
@@ -85,4 +98,64 @@ impl AdvisoryGroupCondition {
             _ => evaluator(self.clone()),
         }
     }
-}
\ No newline at end of file
+
+    /// Evaluate this condition, treating results that aren't known yet as unknown rather than guessing.
+    /// Returns `None` if the result depends on an unknown sub-condition, i.e. `AnyOf` with no matching
+    /// sub-condition but an unknown one. Used to tell whether fetching a group's details could change the result.
+    pub fn evaluate_partial<F>(&self, evaluator: &F) -> Option<bool>
+    where
+        F: Fn(AdvisoryGroupCondition) -> Option<bool>,
+    {
+        match self {
+            AdvisoryGroupCondition::Not { data } => data.evaluate_partial(evaluator).map(|matched| !matched),
+            AdvisoryGroupCondition::AnyOf(conditions) | AdvisoryGroupCondition::AllOf(conditions) => {
+                let any = matches!(self, AdvisoryGroupCondition::AnyOf(_));
+                let mut unknown = false;
+                for condition in conditions {
+                    match condition.evaluate_partial(evaluator) {
+                        Some(matched) if matched == any => return Some(any),
+                        Some(_) => {}
+                        None => unknown = true,
+                    }
+                }
+                if unknown { None } else { Some(!any) }
+            }
+            AdvisoryGroupCondition::None => panic!("AdvisoryGroupCondition::None should never be evaluated"),
+            _ => evaluator(self.clone()),
+        }
+    }
+
+    /// Whether this condition (or any sub-condition) needs the group's details, see [crate::types::GroupDetails].
+    pub fn needs_details(&self) -> bool {
+        match self {
+            AdvisoryGroupCondition::Not { data } => data.needs_details(),
+            AdvisoryGroupCondition::AnyOf(conditions) | AdvisoryGroupCondition::AllOf(conditions) => {
+                conditions.iter().any(|c| c.needs_details())
+            }
+            AdvisoryGroupCondition::CreatedWithinDays(_) | AdvisoryGroupCondition::IsOpen | AdvisoryGroupCondition::IsVerified => true,
+            _ => false,
+        }
+    }
+
+    /// Evaluate a single (non-meta) condition against a group. `None` if the needed information isn't known,
+    /// i.e. the group's details haven't been fetched.
+    pub fn matches_group(&self, group: &PartialGroup) -> Option<bool> {
+        match self {
+            AdvisoryGroupCondition::NameContains(string) => Some(group.name.to_lowercase().contains(&string.to_lowercase())),
+            AdvisoryGroupCondition::OwnerIs(owner_id) => Some(&group.owner_id == owner_id),
+            AdvisoryGroupCondition::Id(id) => Some(&group.id == id),
+            AdvisoryGroupCondition::MemberCountAtMost(count) => group.member_count.map(|members| members <= *count),
+            AdvisoryGroupCondition::CreatedWithinDays(days) => {
+                let created_at = group.details.as_ref()?.created_at?;
+                let created = chrono::DateTime::from_timestamp(created_at, 0)?.naive_utc().date();
+                Some((chrono::Utc::now().date_naive() - created).num_days() <= *days as i64)
+            }
+            AdvisoryGroupCondition::IsOpen => {
+                let join_state = group.details.as_ref()?.join_state.as_ref()?;
+                Some(join_state.eq_ignore_ascii_case("open"))
+            }
+            AdvisoryGroupCondition::IsVerified => group.details.as_ref()?.is_verified,
+            _ => None,
+        }
+    }
+}
@@ -10,6 +10,18 @@ pub enum PerfRank {
     Excellent
 }
 
+impl std::fmt::Display for PerfRank {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            PerfRank::VeryPoor => "Very Poor",
+            PerfRank::Poor => "Poor",
+            PerfRank::Medium => "Medium",
+            PerfRank::Good => "Good",
+            PerfRank::Excellent => "Excellent",
+        })
+    }
+}
+
 impl PerfRank {
     pub fn from_string(s: &str) -> Option<Self> {
         match s.to_lowercase().replace("_", "").as_str() {
@@ -124,6 +136,15 @@ impl GetWorstRank for vrchatapi::models::AvatarPerformance {
     }
 }
 
+/// An avatar a user may be wearing, from avatar search or observed bundle files.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AvatarCandidate {
+    pub avatar_id: String,
+    /// How sure we are that this is the user's avatar, from 0 to 100.
+    pub confidence: u8,
+}
+
 #[derive(Debug, Clone)]
 pub struct AvatarBundleFileMetadata {
     pub file_id: String,
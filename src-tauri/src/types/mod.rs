@@ -4,6 +4,7 @@ use crate::types::avatar::PerfRank;
 pub mod advisories;
 pub mod advisories_groups;
 mod instance_id;
+pub mod notices;
 pub mod user;
 pub mod xsoverlay;
 pub mod avatar;
@@ -16,19 +17,46 @@ pub struct VrcMrdUser {
     pub avatar_name: String,
     pub avatar_id: Option<String>,
     pub avatar_creator: Option<String>,
+    /// The avatar's release status from avatar search (`public` or `private`), if it was found.
+    pub avatar_release_status: Option<String>,
+    /// Avatars the user may be wearing, with how confident we are in each. Used by `AvatarMayBe`.
+    pub avatar_candidates: Vec<avatar::AvatarCandidate>,
     pub avatar_images: Vec<String>,
     pub perf_rank: Option<PerfRank>,
     pub pronouns: Option<String>,
     pub status: Option<String>,
+    pub bio: Option<String>,
+    pub bio_links: Vec<String>,
     pub account_created: Option<i64>, // e.g. "3y"
     pub join_time: i64,               // e.g. "13:12"
     pub leave_time: Option<i64>,      // e.g. "13:24"
     pub advisories: Vec<advisories::ActiveAdvisory>,
     pub age_verified: bool,
+    /// Whether the logged-in account is friends with the user.
+    pub is_friend: bool,
+    /// Whether the user holds a management role in the group hosting the current instance.
+    pub is_group_staff: bool,
     pub recently_kicked: bool,
     pub platform: Option<String>,
     pub trust_rank: Option<user::TrustRank>,
+    /// The user's raw VRChat tags, i.e. `system_trust_basic`, `language_eng`.
+    pub tags: Vec<String>,
+    pub flags: user::UserFlags,
     pub groups: Vec<PartialGroup>,
+    /// Whether the user's groups have been fetched, so an empty `groups` means they aren't in any groups
+    /// rather than that we don't know.
+    pub groups_known: bool,
+    /// Whether the user's info has been loaded from the API (see `update_from`). Until then, fields like
+    /// `age_verified`, `is_friend` and `account_created` are only placeholders, so periodic re-evaluation skips the user.
+    #[serde(default)]
+    pub info_loaded: bool,
+    /// The sum of the weights of the user's matching advisories.
+    pub risk_score: u32,
+    /// The user's highest advisory level, or the level of the escalation rule their risk score passes,
+    /// whichever is higher. `None` if they have no advisories.
+    pub effective_level: Option<advisories::AdvisoryLevel>,
+    /// The ID of the escalation rule that applies to the user, if any.
+    pub escalated_by: Option<String>,
 }
 
 impl VrcMrdUser {
@@ -43,6 +71,23 @@ pub struct PartialGroup {
     pub id: String,
     pub name: String,
     pub owner_id: String,
+    /// Included in the user's group list, so it's known without fetching the group's details.
+    #[serde(default)]
+    pub member_count: Option<u32>,
+    /// Only fetched for groups that could match an active advisory. See [crate::memory::group_details].
+    #[serde(default)]
+    pub details: Option<GroupDetails>,
+}
+
+/// Details of a group that aren't included in a user's group list.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupDetails {
+    /// When the group was created, as a Unix timestamp.
+    pub created_at: Option<i64>,
+    /// Who can join the group, i.e. `open`, `request`, `invite` or `closed`.
+    pub join_state: Option<String>,
+    pub is_verified: Option<bool>,
 }
 
 /* References:
@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::advisories::{AdvisoryLevel, Notice};
+
+/// A notice in History, tagged with where and when it was raised.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ts_rs::TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct StoredNotice {
+    /// The instance the notice was raised in, if known.
+    pub instance_id: Option<String>,
+    /// When the notice was raised, as a Unix timestamp.
+    pub recorded_at: i64,
+    pub notice: Notice,
+}
+
+/// Filters for querying History. Unset filters match every notice.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ts_rs::TS)]
+#[serde(rename_all = "camelCase", default)]
+#[ts(export)]
+pub struct NoticeQuery {
+    /// Only notices at or above this level.
+    pub min_level: Option<AdvisoryLevel>,
+    pub user_id: Option<String>,
+    pub advisory_id: Option<String>,
+    pub instance_id: Option<String>,
+    /// Only notices raised at or after this Unix timestamp.
+    pub from: Option<i64>,
+    /// Only notices raised before this Unix timestamp.
+    pub to: Option<i64>,
+    /// How many matching notices to skip, newest first.
+    pub offset: usize,
+    /// How many notices to return at most. 0 uses the default page size.
+    pub limit: usize,
+}
+
+/// A page of History, newest first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ts_rs::TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct NoticePage {
+    pub notices: Vec<StoredNotice>,
+    /// How many notices match the query in total, across all pages.
+    pub total: usize,
+}
+
+/// A page of the current instance's notices, newest first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ts_rs::TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct NoticeCursorPage {
+    pub notices: Vec<Notice>,
+    /// Pass as `before` to get the next (older) page. `None` if there are no older notices.
+    pub next_cursor: Option<u64>,
+    /// The latest revision when the page was taken. Pass as `since` to get only what changed after it.
+    pub cursor: u64,
+    /// Identifies this run of the app. Pass as `session` along with `cursor`.
+    pub session: String,
+}
+
+/// Notices that were published or changed after a cursor, oldest change first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ts_rs::TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct NoticeDelta {
+    pub notices: Vec<Notice>,
+    /// The latest revision. Pass as `since` next time.
+    pub cursor: u64,
+    /// Identifies this run of the app. Pass as `session` next time.
+    pub session: String,
+    /// Whether the notice list was cleared (i.e. on an instance change or restart) after the cursor,
+    /// or the cursor is from another session.
+    /// If set, drop any notices you have before applying this delta.
+    pub reset: bool,
+}
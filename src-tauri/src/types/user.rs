@@ -13,6 +13,57 @@ pub enum TrustRank {
     Admin,
 }
 
+impl std::fmt::Display for TrustRank {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            TrustRank::Nuisance => "Nuisance",
+            TrustRank::Visitor => "Visitor",
+            TrustRank::NewUser => "New User",
+            TrustRank::User => "User",
+            TrustRank::KnownUser => "Known User",
+            TrustRank::TrustedUser => "Trusted User",
+            TrustRank::Admin => "Admin",
+        })
+    }
+}
+
+/// Explicit flags derived from a user's raw VRChat tags. Unlike [TrustRank], these aren't
+/// collapsed into one value, so i.e. a probable troll with a high trust rank is still flagged.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UserFlags {
+    /// `system_probable_troll`: VRChat suspects the user is a troll.
+    pub probable_troll: bool,
+    /// `system_troll`: VRChat has marked the user as a troll (shown as Nuisance).
+    pub troll: bool,
+    /// `admin_moderator`: a VRChat staff moderator.
+    pub admin: bool,
+    /// `system_supporter`: the user has VRC+.
+    pub supporter: bool,
+    /// `system_early_adopter`: the user supported VRC+ early on.
+    pub early_adopter: bool,
+    /// Languages from `language_***` tags, i.e. `eng`.
+    pub languages: Vec<String>,
+}
+
+impl UserFlags {
+    pub fn from_tags(tags: &[String]) -> Self {
+        let has = |tag: &str| tags.iter().any(|t| t == tag);
+        UserFlags {
+            probable_troll: has("system_probable_troll"),
+            troll: has("system_troll"),
+            admin: has("admin_moderator"),
+            supporter: has("system_supporter"),
+            early_adopter: has("system_early_adopter"),
+            languages: tags
+                .iter()
+                .filter_map(|t| t.strip_prefix("language_"))
+                .map(|l| l.to_string())
+                .collect(),
+        }
+    }
+}
+
 pub trait GetTrustRank {
     fn trust_rank(&self) -> TrustRank;
 }
@@ -8,6 +8,7 @@ import { getCurrentWindow } from "@tauri-apps/api/window";
 import { invoke } from "@tauri-apps/api/core";
 import { useOverlayScrollbars } from "./components/OverlayScrollbarsHook";
 import NoticesList from "./window/main/notices";
+import NoticeHistory from "./window/main/history";
 
 export default function App() {
   if (import.meta.env.PROD) {
@@ -29,6 +30,15 @@ export default function App() {
       unlisten.then(f => f());
     }
   }, []);
+  useEffect(() => {
+    // Advisory actions can ask for a user ID to be copied, which only the webview can do.
+    const unlisten = event.listen<string>("vrcmrd:copy_to_clipboard", ev => {
+      navigator.clipboard.writeText(ev.payload).catch(e => console.error("Failed to copy to clipboard", e));
+    });
+    return () => {
+      unlisten.then(f => f());
+    }
+  }, []);
   useEffect(() => {
     event.emit("vrcmrd:ui-ready", {});
     const unlisten = event.listen("vrcmrd:auth-token-needed", async ev => {
@@ -66,6 +76,7 @@ export default function App() {
               <NoticesList />
             ),
           },
+          { id: "history", label: "History", content: <NoticeHistory /> },
           {
             id: "log",
             label: "Log",
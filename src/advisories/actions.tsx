@@ -0,0 +1,63 @@
+import { AdvisoryAction } from "@app/bindings/AdvisoryAction";
+import DeleteIcon from "mdi-preact/DeleteIcon";
+import PlusIcon from "mdi-preact/PlusIcon";
+
+const inputClass = "w-full p-2 border border-gray-300 dark:border-gray-700 rounded";
+
+const NewActions: { label: string, create: () => AdvisoryAction }[] = [
+  { label: "Add to watchlist", create: () => ({ type: "AddToWatchlist", data: "" }) },
+  { label: "Copy user ID", create: () => ({ type: "CopyUserId" }) },
+  { label: "Webhook", create: () => ({ type: "Webhook", data: { url: "", payload_template: "{\"content\": \"{{:username:}} ({{:user_id:}})\"}" } }) },
+  { label: "Incident log", create: () => ({ type: "IncidentLog", data: { path: "incidents.log", line_template: "{{:username:}} ({{:user_id:}})" } }) },
+  { label: "OSC message", create: () => ({ type: "Osc", data: { target: "127.0.0.1:9000", address: "/vrcmrd/advisory", argument_template: "{{:username:}}" } }) },
+];
+
+export default function ActionsEditor({ actions, setActions }: {
+  actions: AdvisoryAction[],
+  setActions: (actions: AdvisoryAction[]) => void,
+}) {
+  const update = (index: number, action: AdvisoryAction) => setActions(actions.map((a, i) => i === index ? action : a));
+  const remove = (index: number) => setActions(actions.filter((_, i) => i !== index));
+  return <div class="flex flex-col gap-2">
+    {actions.map((action, index) => <div class="flex flex-row gap-2 items-start p-2 border border-gray-300 dark:border-gray-700 rounded">
+      <div class="flex-grow flex flex-col gap-1">
+        <span class="font-bold text-sm">{NewActions.find(a => a.create().type === action.type)?.label ?? action.type}</span>
+        <ActionFields action={action} setAction={(action) => update(index, action)} />
+      </div>
+      <button class="bg-transparent hover:bg-black/20 hover:dark:bg-white/20 hover:text-red-400 transition rounded-full p-1" onClick={() => remove(index)} aria-label="Remove action"><DeleteIcon /></button>
+    </div>)}
+    <div class="flex flex-row gap-2 flex-wrap">
+      {NewActions.map(({ label, create }) => <button class="px-2 py-1 bg-gray-200 dark:bg-gray-700 text-gray-800 dark:text-gray-200 rounded-full text-sm hover:bg-gray-300 dark:hover:bg-gray-600" onClick={() => setActions([...actions, create()])}>
+        <PlusIcon class="inline align-middle w-4 h-4 mr-1 mb-1" />{label}
+      </button>)}
+    </div>
+  </div>;
+}
+
+function ActionFields({ action, setAction }: { action: AdvisoryAction, setAction: (action: AdvisoryAction) => void }) {
+  const value = (e: Event) => (e.target as HTMLInputElement).value;
+  switch (action.type) {
+    case "AddToWatchlist":
+      return <input type="text" class={inputClass} placeholder="Watchlist name" value={action.data} onInput={(e) => setAction({ ...action, data: value(e) })} />;
+    case "CopyUserId":
+      return <p class="text-sm text-gray-600 dark:text-gray-400">Copies the user's ID to the clipboard.</p>;
+    case "Webhook":
+      return <>
+        <input type="url" class={inputClass} placeholder="https://..." value={action.data.url} onInput={(e) => setAction({ ...action, data: { ...action.data, url: value(e) } })} />
+        <textarea class={inputClass} rows={2} value={action.data.payload_template} onInput={(e) => setAction({ ...action, data: { ...action.data, payload_template: value(e) } })} />
+        <p class="text-xs text-gray-600 dark:text-gray-400">Must be JSON. Variables are escaped, so put them inside strings.</p>
+      </>;
+    case "IncidentLog":
+      return <>
+        <input type="text" class={inputClass} placeholder="incidents.log" value={action.data.path} onInput={(e) => setAction({ ...action, data: { ...action.data, path: value(e) } })} />
+        <p class="text-xs text-gray-600 dark:text-gray-400">Saved in the incident_logs folder in VRCMRD's data folder.</p>
+        <input type="text" class={inputClass} value={action.data.line_template} onInput={(e) => setAction({ ...action, data: { ...action.data, line_template: value(e) } })} />
+      </>;
+    case "Osc":
+      return <>
+        <input type="text" class={inputClass} placeholder="127.0.0.1:9000" value={action.data.target} onInput={(e) => setAction({ ...action, data: { ...action.data, target: value(e) } })} />
+        <input type="text" class={inputClass} placeholder="/address" value={action.data.address} onInput={(e) => setAction({ ...action, data: { ...action.data, address: value(e) } })} />
+        <input type="text" class={inputClass} value={action.data.argument_template} onInput={(e) => setAction({ ...action, data: { ...action.data, argument_template: value(e) } })} />
+      </>;
+  }
+}
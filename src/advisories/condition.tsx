@@ -10,6 +10,7 @@ import AndroidIcon from "mdi-preact/AndroidIcon";
 import MonitorIcon from "mdi-preact/MonitorIcon";
 import { menu } from "@tauri-apps/api";
 import { LogicalPosition, PhysicalPosition, Position } from "@tauri-apps/api/dpi";
+import { toDateTimeLocal } from "./editor";
 
 export default function ConditionEditor({ condition, depth, setCondition, addSibling, removeCondition }: { 
   condition: AdvisoryCondition,
@@ -18,7 +19,7 @@ export default function ConditionEditor({ condition, depth, setCondition, addSib
   addSibling?: (condition: AdvisoryCondition) => void,
   removeCondition?: (() => void)
 }) {
-  const singleStringConditions: AdvisoryCondition["type"][] = ["Is", "UsernameContains", "StatusContains", "PronounContains", "AvatarMayBe", "AvatarNameContains", "AvatarCreatorIs", "IsGroupMember", "InstanceOwner"];
+  const singleStringConditions: AdvisoryCondition["type"][] = ["Is", "UsernameContains", "StatusContains", "BioContains", "BioMatches", "BioLinkDomainIs", "HasTag", "TagPrefix", "PronounContains", "AvatarNameContains", "AvatarCreatorIs", "AvatarImageFileIs", "IsGroupMember", "InstanceOwner", "InWatchlist"];
   function cycleConditionType(e: Event) {
     // AllOf -> AnyOf -> Not -> AllOf
     if (condition.type === "AllOf") {
@@ -196,6 +197,67 @@ export default function ConditionEditor({ condition, depth, setCondition, addSib
       </div>
       <RemoveConditionButton onClick={removeCondition} setCondition={setCondition} />
     </div>
+  } else if (condition.type === "IsUntil") {
+    return <div onContextMenu={(e) => {e.preventDefault(); e.stopPropagation(); showMenu();}} class="border border-gray-300 dark:border-gray-600 only:rounded first:rounded-t last:rounded-b not-last:border-b-0 bg-gray-200 dark:bg-gray-700 p-2 flex flex-row gap-2 items-start w-full">
+      {depth >= 2 && <ReorderConditionHandle onClick={() => showMenu()} onDrag={null} />}
+      <label class="font-bold w-[32ch] overflow-hidden text-wrap my-2">{ConditionLabel(condition)}</label>
+      <div class="w-full flex flex-col gap-1/2 flex-grow">
+        <span class="text-xs italic text-gray-400">User ID (usually usr_***)</span>
+        <input type="text" class="w-full bg-transparent border border-gray-300 dark:border-gray-600 rounded p-1 mt-1 flex-grow" value={condition.data.id} onInput={(e) => setCondition({...condition, data: {...condition.data, id: (e.target as HTMLInputElement).value}})} />
+        <span class="text-xs italic text-gray-400 mt-1">Until</span>
+        <input type="datetime-local" required class="w-full bg-transparent border border-gray-300 dark:border-gray-600 rounded p-1 mt-1 flex-grow" value={condition.data.expires_at ? toDateTimeLocal(condition.data.expires_at) : ""} onInput={(e) => {
+          const value = (e.target as HTMLInputElement).value;
+          // An entry needs an end; ignore clearing (or partial input) instead of saving an empty timestamp
+          if (!value || isNaN(new Date(value).getTime())) return;
+          setCondition({...condition, data: {...condition.data, expires_at: new Date(value).toISOString()}});
+        }} />
+      </div>
+      <RemoveConditionButton onClick={removeCondition} setCondition={setCondition} />
+    </div>
+  } else if (condition.type === "AvatarMayBe") {
+    return <div onContextMenu={(e) => {e.preventDefault(); e.stopPropagation(); showMenu();}} class="border border-gray-300 dark:border-gray-600 only:rounded first:rounded-t last:rounded-b not-last:border-b-0 bg-gray-200 dark:bg-gray-700 p-2 flex flex-row gap-2 items-start w-full">
+      {depth >= 2 && <ReorderConditionHandle onClick={() => showMenu()} onDrag={null} />}
+      <label class="font-bold w-[32ch] overflow-hidden text-wrap my-2">{ConditionLabel(condition)}</label>
+      <div class="w-full flex flex-col gap-1/2 flex-grow">
+        <span class="text-xs italic text-gray-400">Avatar ID (avtr_***)</span>
+        <input type="text" class="w-full bg-transparent border border-gray-300 dark:border-gray-600 rounded p-1 mt-1 flex-grow" value={condition.data.avatar_id} onInput={(e) => setCondition({...condition, data: {...condition.data, avatar_id: (e.target as HTMLInputElement).value}})} />
+        <span class="text-xs italic text-gray-400 mt-1">Minimum confidence (%). Other avatars by the same creator are 20%, avatars found by image or name 90-100%.</span>
+        <input type="number" min={0} max={100} class="w-full bg-transparent border border-gray-300 dark:border-gray-600 rounded p-1 mt-1 flex-grow text-end" value={condition.data.min_confidence} onInput={(e) => setCondition({...condition, data: {...condition.data, min_confidence: Math.min(100, Math.max(0, parseInt((e.target as HTMLInputElement).value) || 0))}})} />
+      </div>
+      <RemoveConditionButton onClick={removeCondition} setCondition={setCondition} />
+    </div>
+  } else if (condition.type === "HasGroupRole") {
+    return <div onContextMenu={(e) => {e.preventDefault(); e.stopPropagation(); showMenu();}} class="border border-gray-300 dark:border-gray-600 only:rounded first:rounded-t last:rounded-b not-last:border-b-0 bg-gray-200 dark:bg-gray-700 p-2 flex flex-row gap-2 items-start w-full">
+      {depth >= 2 && <ReorderConditionHandle onClick={() => showMenu()} onDrag={null} />}
+      <label class="font-bold w-[32ch] overflow-hidden text-wrap my-2">{ConditionLabel(condition)}</label>
+      <div class="w-full flex flex-col gap-1/2 flex-grow">
+        <span class="text-xs italic text-gray-400">Group ID (grp_***). Leave empty for the group hosting the instance.</span>
+        <input type="text" class="w-full bg-transparent border border-gray-300 dark:border-gray-600 rounded p-1 mt-1 flex-grow" value={condition.data.group_id} onInput={(e) => setCondition({...condition, data: {...condition.data, group_id: (e.target as HTMLInputElement).value}})} />
+        <span class="text-xs italic text-gray-400 mt-1">Role ID (grol_***). Leave empty for any staff role.</span>
+        <input type="text" class="w-full bg-transparent border border-gray-300 dark:border-gray-600 rounded p-1 mt-1 flex-grow" value={condition.data.role_id} onInput={(e) => setCondition({...condition, data: {...condition.data, role_id: (e.target as HTMLInputElement).value}})} />
+      </div>
+      <RemoveConditionButton onClick={removeCondition} setCondition={setCondition} />
+    </div>
+  } else if (condition.type === "RecentlyKickedWithinHours" || condition.type === "RecentlyBannedWithinHours") {
+    return <div onContextMenu={(e) => {e.preventDefault(); e.stopPropagation(); showMenu();}} class="border border-gray-300 dark:border-gray-600 only:rounded first:rounded-t last:rounded-b not-last:border-b-0 bg-gray-200 dark:bg-gray-700 p-2 flex flex-row gap-2 items-stretch w-full">
+      {depth >= 2 && <ReorderConditionHandle onClick={() => showMenu()} onDrag={null} />}
+      <label class="font-bold w-[32ch] overflow-hidden text-wrap my-2">{ConditionLabel(condition)}</label>
+      <div class="w-full flex flex-col gap-1/2 flex-grow">
+        <input type="number" min={1} class="w-full bg-transparent border border-gray-300 dark:border-gray-600 rounded p-1 mt-1 flex-grow text-end" value={condition.data} onInput={(e) => setCondition({...condition, data: parseInt((e.target as HTMLInputElement).value) || 0})} />
+      </div>
+      <span class="text-sm italic text-gray-400 self-center mx-2">hours</span>
+      <RemoveConditionButton onClick={removeCondition} setCondition={setCondition} />
+    </div>
+  } else if (condition.type === "TimeInInstanceAtLeastMinutes") {
+    return <div onContextMenu={(e) => {e.preventDefault(); e.stopPropagation(); showMenu();}} class="border border-gray-300 dark:border-gray-600 only:rounded first:rounded-t last:rounded-b not-last:border-b-0 bg-gray-200 dark:bg-gray-700 p-2 flex flex-row gap-2 items-stretch w-full">
+      {depth >= 2 && <ReorderConditionHandle onClick={() => showMenu()} onDrag={null} />}
+      <label class="font-bold w-[32ch] overflow-hidden text-wrap my-2">{ConditionLabel(condition)}</label>
+      <div class="w-full flex flex-col gap-1/2 flex-grow">
+        <input type="number" min={0} class="w-full bg-transparent border border-gray-300 dark:border-gray-600 rounded p-1 mt-1 flex-grow text-end" value={condition.data} onInput={(e) => setCondition({...condition, data: parseInt((e.target as HTMLInputElement).value) || 0})} />
+      </div>
+      <span class="text-sm italic text-gray-400 self-center mx-2">minutes</span>
+      <RemoveConditionButton onClick={removeCondition} setCondition={setCondition} />
+    </div>
   } else if (condition.type === "AccountAgeAtMostDays") {
     return <div onContextMenu={(e) => {e.preventDefault(); e.stopPropagation(); showMenu();}} class="border border-gray-300 dark:border-gray-600 only:rounded first:rounded-t last:rounded-b not-last:border-b-0 bg-gray-200 dark:bg-gray-700 p-2 flex flex-row gap-2 items-stretch w-full">
       {depth >= 2 && <ReorderConditionHandle onClick={() => showMenu()} onDrag={null} />}
@@ -250,7 +312,24 @@ export default function ConditionEditor({ condition, depth, setCondition, addSib
         <GroupConditionEditor condition={condition.data} setCondition={(newCondition) => setCondition({...condition, data: newCondition})} removeCondition={removeCondition} />
       </div>
     </details>
-  } else if (condition.type === "AgeNotVerified" || condition.type === "InstanceGroupRestricted") {
+  } else if (condition.type === "GroupCountAtLeast") {
+    return <details onContextMenu={(e) => {e.preventDefault(); e.stopPropagation(); showMenu();}} class="border border-gray-300 dark:border-gray-700 !border-l-green-400 border-l-4 only:rounded first:rounded-t last:rounded-b not-last:border-b-0 flex flex-col items-stretch w-full" open>
+      <summary class="flex flex-row gap-2 w-full items-center p-2">
+        {depth >= 2 && <ReorderConditionHandle onClick={() => showMenu()} onDrag={null} />}
+        <RightIcon class="[details[open]>summary>&]:hidden cursor-pointer" />
+        <DownIcon class="hidden [details[open]>summary>&]:inline-block" />
+        <span class="font-bold overflow-hidden text-wrap my-2">In at least</span>
+        <input type="number" min={0} class="w-[8ch] bg-transparent border border-gray-300 dark:border-gray-600 rounded p-1 text-end" value={condition.data.count} onInput={(e) => setCondition({...condition, data: {...condition.data, count: parseInt((e.target as HTMLInputElement).value) || 0}})} />
+        <span class="font-bold overflow-hidden text-wrap my-2">groups matching this condition</span>
+        <span class="flex-grow" />
+        <RemoveConditionButton onClick={removeCondition} setCondition={setCondition} />
+      </summary>
+      <div class="p-2 group-last:rounded-bl group-only:rounded-bl">
+        <span class="text-xs italic text-gray-400">An empty "All of" counts every group.</span>
+        <GroupConditionEditor condition={condition.data.condition} setCondition={(newCondition) => setCondition({...condition, data: {...condition.data, condition: newCondition}})} />
+      </div>
+    </details>
+  } else if (condition.type === "AgeNotVerified" || condition.type === "IsFriend" || condition.type === "IsStaffTrusted" || condition.type === "AvatarIsPrivate" || condition.type === "AvatarIsPublic" || condition.type === "InstanceGroupRestricted") {
     return <div onContextMenu={(e) => {e.preventDefault(); e.stopPropagation(); showMenu();}} class="border border-gray-300 dark:border-gray-600 only:rounded first:rounded-t last:rounded-b not-last:border-b-0 bg-gray-200 dark:bg-gray-700 p-2 flex flex-row gap-2 items-center w-full">
       {depth >= 2 && <ReorderConditionHandle onClick={() => showMenu()} onDrag={null} />}
       <span class="font-bold w-[32ch] overflow-hidden text-wrap">{ConditionLabel(condition)}</span>
@@ -286,18 +365,35 @@ export function NewCondition({ setCondition }: { setCondition: (condition: Advis
       {active: false, set: () => setCondition({type: "AnyOf", data: []}), label: <>Any of...</>, description: <>At least one sub-condition must be met. (OR between each condition.)</>},
       {active: false, set: () => setCondition({type: "Not", data: {data: {type: "None"}}}), label: <>Not...</>, description: <>The sub-condition must NOT be met. (Inverts the sub-condition.)</>},
       {active: false, set: () => setCondition({type: "GroupCondition", data: {type: "None"}}), label: <>In a group matching condition...</>, description: <>The condition applies to each group the user is in, and if any group meets the condition, the user matches.</>},
+      {active: false, set: () => setCondition({type: "GroupCountAtLeast", data: {condition: {type: "AllOf", data: []}, count: 1}}), label: <>In at least N groups matching condition...</>, description: <>Counts the user's groups that meet the condition. Wrap in "Not..." for "fewer than N groups".</>},
       {active: false, set: () => setCondition({type: "Is", data: ""}), label: <>User ID is</>},
+      {active: false, set: () => setCondition({type: "IsUntil", data: {id: "", expires_at: new Date(Date.now() + 7 * 24 * 60 * 60 * 1000).toISOString()}}), label: <>User ID is (until)</>, description: <>For short-term watches. Removed from "Any of" lists automatically once it expires.</>},
       {active: false, set: () => setCondition({type: "UsernameContains", data: ""}), label: <>Username contains</>},
       {active: false, set: () => setCondition({type: "StatusContains", data: ""}), label: <>Status contains</>},
       {active: false, set: () => setCondition({type: "PronounContains", data: ""}), label: <>Pronouns contain</>},
+      {active: false, set: () => setCondition({type: "BioContains", data: ""}), label: <>Bio contains</>},
+      {active: false, set: () => setCondition({type: "BioMatches", data: ""}), label: <>Bio matches pattern</>, description: <>A regular expression, matched case-insensitively</>},
+      {active: false, set: () => setCondition({type: "BioLinkDomainIs", data: ""}), label: <>Bio links to domain</>, description: <>i.e. a known crasher Discord server's invite domain</>},
       {active: false, set: () => setCondition({type: "AccountAgeAtMostDays", data: 0}), label: <>Account age</>},
-      {active: false, set: () => setCondition({type: "AvatarMayBe", data: ""}), label: <>Avatar</>, description: <>One of a list of possibly-equipped avatars</>},
+      {active: false, set: () => setCondition({type: "AvatarMayBe", data: {avatar_id: "", min_confidence: 70}}), label: <>Avatar</>, description: <>One of a list of possibly-equipped avatars</>},
       {active: false, set: () => setCondition({type: "AvatarNameContains", data: ""}), label: <>Avatar name contains</>, description: <>Useful to find types of avatar that are commonly used by trolls</>},
       {active: false, set: () => setCondition({type: "AvatarCreatorIs", data: ""}), label: <>Avatar creator ID is</>, description: <>The ID of the user who created the avatar</>},
+      {active: false, set: () => setCondition({type: "AvatarIsPrivate"}), label: <>Avatar is private</>, description: <>Only known once the avatar is found by avatar search</>},
+      {active: false, set: () => setCondition({type: "AvatarIsPublic"}), label: <>Avatar is public</>, description: <>Only known once the avatar is found by avatar search</>},
+      {active: false, set: () => setCondition({type: "AvatarImageFileIs", data: ""}), label: <>Avatar image file is</>, description: <>Matches a known avatar by its image file, even if the avatar ID can't be found</>},
       {active: false, set: () => setCondition({type: "IsGroupMember", data: ""}), label: <>Is member of group</>},
       {active: false, set: () => setCondition({type: "AgeNotVerified"}), label: <>Not 18+ age-verified</>, description: <>Users who have not ID-verified with VRChat</>},
+      {active: false, set: () => setCondition({type: "RecentlyKickedWithinHours", data: 24}), label: <>Recently kicked</>, description: <>Kicked from an instance you were in, even if they rejoined since</>},
+      {active: false, set: () => setCondition({type: "RecentlyBannedWithinHours", data: 24}), label: <>Recently banned</>, description: <>Banned from an instance you were in</>},
+      {active: false, set: () => setCondition({type: "TimeInInstanceAtLeastMinutes", data: 30}), label: <>Time in instance</>, description: <>In the instance for at least this long. Checked periodically, not just on join.</>},
+      {active: false, set: () => setCondition({type: "IsFriend"}), label: <>Is my friend</>, description: <>Friends with the logged-in account. Wrap in "Not..." to match strangers.</>},
+      {active: false, set: () => setCondition({type: "IsStaffTrusted"}), label: <>Trusted by staff</>, description: <>On the moderator-trusted user list. Wrap in "Not..." to match users staff doesn't know.</>},
+      {active: false, set: () => setCondition({type: "InWatchlist", data: ""}), label: <>On watchlist</>, description: <>Added by an advisory's "Add to watchlist" action</>},
+      {active: false, set: () => setCondition({type: "HasGroupRole", data: {group_id: "", role_id: ""}}), label: <>Has group role</>, description: <>A role in the group hosting the instance. Wrap in "Not..." to exempt group staff.</>},
       {active: false, set: () => setCondition({type: "PlatformIs", data: ""}), label: <>Platform</>},
       {active: false, set: () => setCondition({type: "TrustRankAtMost", data: "Nuisance"}), label: <>Max trust rank</>},
+      {active: false, set: () => setCondition({type: "HasTag", data: "system_probable_troll"}), label: <>Has VRChat tag</>, description: <>A raw tag, i.e. system_probable_troll. Not hidden by trust rank.</>},
+      {active: false, set: () => setCondition({type: "TagPrefix", data: ""}), label: <>Has VRChat tag starting with</>, description: <>i.e. language_</>},
       {active: false, set: () => setCondition({type: "InstanceGroupRestricted", data: null}), label: <>Group-only or Group+ Instance</>},
       {active: false, set: () => setCondition({type: "InstanceOwner", data: ""}), label: <>In instance owned by</>, description: <>The owner of the instance. Either a user or a group.</>},
   ]} />
@@ -306,30 +402,52 @@ export function NewCondition({ setCondition }: { setCondition: (condition: Advis
 export const ConditionLabel = (condition: AdvisoryCondition) => {
   switch (condition.type) {
     case "Is": return <>User ID is</>;
+    case "IsUntil": return <>User ID is (until)</>;
     case "UsernameContains": return <>Username contains</>;
     case "StatusContains": return <>Status contains</>;
     case "PronounContains": return <>Pronouns contain</>;
+    case "BioContains": return <>Bio contains</>;
+    case "BioMatches": return <>Bio matches pattern</>;
+    case "BioLinkDomainIs": return <>Bio links to domain</>;
     case "AvatarMayBe": return <>Wearing avatar</>;
     case "AvatarNameContains": return <>Avatar name contains</>;
     case "AvatarCreatorIs": return <>Avatar creator ID is</>;
+    case "AvatarIsPrivate": return <>Avatar is private</>;
+    case "AvatarIsPublic": return <>Avatar is public</>;
+    case "AvatarImageFileIs": return <>Avatar image file is</>;
     case "IsGroupMember": return <>Is member of group</>;
     case "InstanceOwner": return <>In instance owned by</>;
     case "InstanceGroupRestricted": return <>In Group-only or Group+ instance</>;
     case "AccountAgeAtMostDays": return <>Account age</>;
     case "PlatformIs": return <>Platform</>;
     case "TrustRankAtMost": return <>Max trust rank</>;
+    case "HasTag": return <>Has VRChat tag</>;
+    case "TagPrefix": return <>Has VRChat tag starting with</>;
     case "AgeNotVerified": return <>Not 18+ age-verified</>;
+    case "RecentlyKickedWithinHours": return <>Kicked within the last</>;
+    case "RecentlyBannedWithinHours": return <>Banned within the last</>;
+    case "TimeInInstanceAtLeastMinutes": return <>In the instance for at least</>;
+    case "IsFriend": return <>Is my friend</>;
+    case "IsStaffTrusted": return <>Trusted by staff</>;
+    case "InWatchlist": return <>On watchlist</>;
+    case "HasGroupRole": return <>Has group role</>;
+    case "GroupCountAtLeast": return <>In at least N groups matching condition</>;
     default: return condition.type;
   }
 }
 
 export const ConditionInputTip = (condition: AdvisoryCondition) => {
   switch (condition.type) {
-    case "AvatarMayBe": return <>Avatar ID (avtr_***)</>;
     case "IsGroupMember": return <>Group ID (grp_***)</>;
     case "InstanceOwner": return <>User or group ID (usually usr_*** or grp_***)</>;
     case "Is": return <>User ID (usually usr_***)</>;
     case "AvatarCreatorIs": return <>User ID (usually usr_***)</>;
+    case "AvatarImageFileIs": return <>File ID (file_***), from the avatar's image URL</>;
+    case "HasTag": return <>Tag, i.e. system_probable_troll or language_eng</>;
+    case "TagPrefix": return <>Tag prefix, i.e. language_</>;
+    case "BioMatches": return <>Regular expression, i.e. discord\.gg/\w+</>;
+    case "BioLinkDomainIs": return <>Domain, i.e. discord.gg (includes subdomains)</>;
+    case "InWatchlist": return <>Watchlist name</>;
     default: return null;
   }
 }
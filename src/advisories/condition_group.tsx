@@ -187,6 +187,23 @@ export default function GroupConditionEditor({ condition, setCondition, removeCo
       </div>
       <RemoveConditionButton onClick={removeCondition} setCondition={setCondition} />
     </div>
+  } else if (condition.type === "MemberCountAtMost" || condition.type === "CreatedWithinDays") {
+    return <div onContextMenu={(e) => {e.preventDefault(); e.stopPropagation(); showMenu();}} class="border border-gray-300 dark:border-gray-600 only:rounded first:rounded-t last:rounded-b not-last:border-b-0 bg-gray-200 dark:bg-gray-700 p-2 flex flex-row gap-2 items-stretch w-full">
+      <ReorderConditionHandle onClick={() => showMenu()} onDrag={null} />
+      <label class="font-bold w-[32ch] overflow-hidden text-wrap my-2">{ConditionLabel(condition)}</label>
+      <div class="w-full flex flex-col gap-1/2 flex-grow">
+        <input type="number" min={0} class="w-full bg-transparent border border-gray-300 dark:border-gray-600 rounded p-1 mt-1 flex-grow text-end" value={condition.data} onInput={(e) => setCondition({...condition, data: parseInt((e.target as HTMLInputElement).value) || 0})} />
+      </div>
+      <span class="text-sm italic text-gray-400 self-center mx-2">{condition.type === "MemberCountAtMost" ? "members" : "days"}</span>
+      <RemoveConditionButton onClick={removeCondition} setCondition={setCondition} />
+    </div>
+  } else if (condition.type === "IsOpen" || condition.type === "IsVerified") {
+    return <div onContextMenu={(e) => {e.preventDefault(); e.stopPropagation(); showMenu();}} class="border border-gray-300 dark:border-gray-600 only:rounded first:rounded-t last:rounded-b not-last:border-b-0 bg-gray-200 dark:bg-gray-700 p-2 flex flex-row gap-2 items-center w-full">
+      <ReorderConditionHandle onClick={() => showMenu()} onDrag={null} />
+      <span class="font-bold w-[32ch] overflow-hidden text-wrap">{ConditionLabel(condition)}</span>
+      <span class="flex-grow" />
+      <RemoveConditionButton onClick={removeCondition} setCondition={setCondition} />
+    </div>
   } else {
     return <div onContextMenu={(e) => {e.preventDefault(); e.stopPropagation(); showMenu();}} class="border border-gray-300 dark:border-gray-600 only:rounded first:rounded-t last:rounded-b not-last:border-b-0 bg-gray-200 dark:bg-gray-700 p-2 flex flex-row gap-2 items-center w-full">
       <ReorderConditionHandle onClick={() => showMenu()} onDrag={null} />
@@ -209,6 +226,10 @@ export function NewCondition({ setCondition }: { setCondition: (condition: Advis
       {active: false, set: () => setCondition({type: "NameContains", data: ""}), label: <>Group name contains</>},
       {active: false, set: () => setCondition({type: "Id", data: ""}), label: <>Group ID is</>, description: <>The group has the given ID (e.g. "grp_***"). You should use "Is member of group" instead if you're checking for membership in a known group (it's far simpler). This is instead for cases where you want to match some conditions but exclude certain known groups.</>},
       {active: false, set: () => setCondition({type: "OwnerIs", data: ""}), label: <>Group owner is</>, description: <>The owner of the group. Useful for conditioning advisories for groups on its owner, such as known abusers or trusted community members.</>},
+      {active: false, set: () => setCondition({type: "MemberCountAtMost", data: 10}), label: <>Member count at most</>, description: <>The group has at most this many members. Throwaway groups tend to be tiny.</>},
+      {active: false, set: () => setCondition({type: "CreatedWithinDays", data: 7}), label: <>Created within</>, description: <>The group was created within this many days. Fetches the group's details when needed.</>},
+      {active: false, set: () => setCondition({type: "IsOpen"}), label: <>Group is open</>, description: <>Anyone can join the group, without an invite or request. Fetches the group's details when needed.</>},
+      {active: false, set: () => setCondition({type: "IsVerified"}), label: <>Group is verified</>, description: <>The group is verified by VRChat. Fetches the group's details when needed.</>},
   ]} />
 }
 
@@ -217,6 +238,10 @@ export const ConditionLabel = (condition: AdvisoryGroupCondition) => {
     case "Id": return <>Group ID is</>;
     case "OwnerIs": return <>Group owner is</>;
     case "NameContains": return <>Group name contains</>;
+    case "MemberCountAtMost": return <>Member count at most</>;
+    case "CreatedWithinDays": return <>Group created within</>;
+    case "IsOpen": return <>Group is open</>;
+    case "IsVerified": return <>Group is verified</>;
     default: return condition.type;
   }
 }
@@ -16,5 +16,9 @@ export default function defaultAdvisory(): Advisory {
         send_notification: false,
         send_tts: false,
         tags: [],
+        cooldown_minutes: null,
+        expires_at: null,
+        actions: [],
+        weight: null,
     };
 }
\ No newline at end of file
@@ -7,12 +7,14 @@ import InfoOutlineIcon from "mdi-preact/InformationOutlineIcon";
 import InfoFilledIcon from "mdi-preact/InformationIcon";
 import ErrorIcon from "mdi-preact/AlertIcon";
 import StopIcon from "mdi-preact/AlertOctagonIcon";
-import { useState } from "preact/compat";
+import { useEffect, useState } from "preact/compat";
 import { NestedConditionTypes } from "./condition";
 import ConditionEditor from "./condition";
 import Dropdown from "../components/Dropdown";
 import PlusIcon from "mdi-preact/PlusIcon";
 import { NestedGroupConditionTypesAlt } from "./condition_group";
+import ActionsEditor from "./actions";
+import { TemplateVariable } from "@app/bindings/TemplateVariable";
 
 export default function AdvisoryEditor({ advisory, isNew, setOverlay, setDialog }: { 
   advisory: Advisory,
@@ -29,6 +31,15 @@ export default function AdvisoryEditor({ advisory, isNew, setOverlay, setDialog
   const [sendTts, setSendTts] = useState(advisory.send_tts);
   const [condition, setCondition] = useState(advisory.condition);
   const [tags, setTags] = useState(advisory.tags);
+  const [cooldownMinutes, setCooldownMinutes] = useState(advisory.cooldown_minutes);
+  const [weight, setWeight] = useState(advisory.weight ?? null);
+  const [expiresAt, setExpiresAt] = useState(advisory.expires_at);
+  const [actions, setActions] = useState(advisory.actions ?? []);
+  const [templateVariables, setTemplateVariables] = useState<TemplateVariable[]>([]);
+  const [saveError, setSaveError] = useState<string | null>(null);
+  useEffect(() => {
+    invoke<TemplateVariable[]>("get_template_variables").then(setTemplateVariables);
+  }, []);
   return <div class="select-none h-full w-full p-2"><div class="max-w-3xl ml-auto flex flex-col bg-white dark:bg-gray-800 text-gray-900 dark:text-gray-100 h-full w-full px-6 py-2 rounded shadow-lg">
     <div class="w-full flex flex-row mb-4 gap-2 items-center">
       <button class="inline-block bg-transparent hover:bg-black/20 hover:dark:bg-white/20 text-white transition rounded-full p-2 m-2" onClick={() => setOverlay?.(null)} aria-label="Close"><CloseIcon /></button>
@@ -95,8 +106,16 @@ export default function AdvisoryEditor({ advisory, isNew, setOverlay, setDialog
             const insert = "{{:avatar_name:}}";
             setMessageTemplate(messageTemplate + insert);
           }}><PlusIcon class="inline align-middle w-4 h-4 mr-1 mb-1" />Avatar Name</button>}
+          {(NestedConditionTypes(condition).includes("BioContains") || NestedConditionTypes(condition).includes("BioMatches") || NestedConditionTypes(condition).includes("BioLinkDomainIs")) && <button class="px-2 py-1 bg-gray-200 dark:bg-gray-700 text-gray-800 dark:text-gray-200 rounded-full text-sm hover:bg-gray-300 dark:hover:bg-gray-600" onClick={() => {
+            const insert = "{{:bio_match:}}";
+            setMessageTemplate(messageTemplate + insert);
+          }}><PlusIcon class="inline align-middle w-4 h-4 mr-1 mb-1" />Bio Match</button>}
+          {templateVariables.filter(v => v.alwaysAvailable && !["username", "avatar_name", "account_age_days"].includes(v.name)).map(v => <button class="px-2 py-1 bg-gray-200 dark:bg-gray-700 text-gray-800 dark:text-gray-200 rounded-full text-sm hover:bg-gray-300 dark:hover:bg-gray-600" title={v.description} onClick={() => {
+            const insert = v.name === "join_time" ? "{{:join_time|relative_time:}}" : `{{:${v.name}:}}`;
+            setMessageTemplate(messageTemplate + insert);
+          }}><PlusIcon class="inline align-middle w-4 h-4 mr-1 mb-1" />{v.name}</button>)}
         </div>
-        <p class="mb-2 text-sm text-gray-600 dark:text-gray-400">The message that will be shown or spoken when this advisory is applied. You can use variables like <code>{'{{:variable||default:}}'}</code> to include specific context.</p>
+        <p class="mb-2 text-sm text-gray-600 dark:text-gray-400">The message that will be shown or spoken when this advisory is applied. You can use variables like <code>{'{{:variable||default:}}'}</code> to include specific context, filters like <code>{'{{:username|upper:}}'}</code> or <code>{'{{:status|truncate:20:}}'}</code>, and conditionals like <code>{'{{:#if pronouns:}}({{:pronouns:}}){{:/if:}}'}</code>.</p>
       </div>
       <div class="my-4 flex flex-col gap-2">
         {/* Tags: turn into chips when a comma is inserted (like email addresses in a Gmail "To" box) */}
@@ -115,15 +134,52 @@ export default function AdvisoryEditor({ advisory, isNew, setOverlay, setDialog
         <input id="send-tts-input" type="checkbox" checked={sendTts} onChange={(e) => setSendTts((e.target as HTMLInputElement).checked)} />
         <label class="font-bold" for="send-tts-input">Speak message via TTS</label>
       </div>
+      <div class="my-4 flex flex-col gap-2">
+        <label class="font-bold" for="weight-input">Risk weight:</label>
+        <input id="weight-input" type="number" min={0} class="w-full p-2 border border-gray-300 dark:border-gray-700 rounded" value={weight ?? ""} placeholder={String({ 0: 0, 1: 1, 2: 2, 3: 4, 4: 8 }[level as any as number] ?? 0)} onInput={(e) => {
+          const value = parseInt((e.target as HTMLInputElement).value);
+          setWeight(isNaN(value) || value < 0 ? null : value);
+        }} />
+        <p class="mb-2 text-sm text-gray-600 dark:text-gray-400">How much this advisory adds to a user's risk score. Escalation rules raise a user's level when their score passes a threshold. Leave empty to use the default for the level.</p>
+      </div>
+      <div class="my-4 flex flex-col gap-2">
+        <label class="font-bold" for="cooldown-input">Cooldown (minutes):</label>
+        <input id="cooldown-input" type="number" min={0} class="w-full p-2 border border-gray-300 dark:border-gray-700 rounded" value={cooldownMinutes ?? ""} onInput={(e) => {
+          const value = parseInt((e.target as HTMLInputElement).value);
+          setCooldownMinutes(isNaN(value) || value <= 0 ? null : value);
+        }} />
+        <p class="mb-2 text-sm text-gray-600 dark:text-gray-400">If a user matches this advisory again within this many minutes (i.e. by rejoining), they won't trigger another notification or TTS message. Leave empty to always notify.</p>
+      </div>
+      <div class="my-4 flex flex-col gap-2">
+        <label class="font-bold" for="expires-at-input">Expires:</label>
+        <input id="expires-at-input" type="datetime-local" class="w-full p-2 border border-gray-300 dark:border-gray-700 rounded" value={expiresAt ? toDateTimeLocal(expiresAt) : ""} onInput={(e) => {
+          const value = (e.target as HTMLInputElement).value;
+          setExpiresAt(value && !isNaN(new Date(value).getTime()) ? new Date(value).toISOString() : null);
+        }} />
+        <p class="mb-2 text-sm text-gray-600 dark:text-gray-400">The advisory is deactivated automatically after this time. Leave empty to keep it indefinitely.</p>
+      </div>
+      <div class="my-4 flex flex-col gap-2">
+        <span class="font-bold">Actions:</span>
+        <ActionsEditor actions={actions} setActions={setActions} />
+        <p class="mb-2 text-sm text-gray-600 dark:text-gray-400">Run when a user newly matches this advisory. Templates can use the same variables as the message, plus <code>{'{{:user_id:}}'}</code>.</p>
+      </div>
+      {saveError && <p class="text-sm text-red-500">{saveError}</p>}
       <div class="mt-6 flex flex-row gap-4 justify-end">
         {/* <button class="px-4 py-2 hover:bg-gray-200 dark:hover:bg-gray-700 text-white rounded" onClick={() => setOverlay?.(null)}>Cancel</button>*/}
         <button class="px-4 py-2 bg-blue-600 hover:bg-blue-700 active:bg-blue-800 text-white rounded" onClick={() => {
-          const updatedAdvisory: Advisory = { ...advisory, active, name, message_template: messageTemplate, level, send_notification: sendNotification, send_tts: sendTts, private: privateAdvisory, tags, condition };
+          const updatedAdvisory: Advisory = { ...advisory, active, name, message_template: messageTemplate, level, send_notification: sendNotification, send_tts: sendTts, private: privateAdvisory, tags, condition, cooldown_minutes: cooldownMinutes, expires_at: expiresAt, actions, weight };
           invoke(isNew ? "add_advisory" : "update_advisory", { advisory: updatedAdvisory }).then(() => {
             setOverlay?.(null);
-          });
+          }).catch(e => setSaveError(String(e)));
         }}>Save Changes</button>
       </div>
     </div>
   </div></div>;
-}
\ No newline at end of file
+}
+
+/** Format an RFC 3339 timestamp for a `datetime-local` input, in local time. */
+export function toDateTimeLocal(timestamp: string): string {
+  const date = new Date(timestamp);
+  const offset = date.getTimezoneOffset() * 60 * 1000;
+  return new Date(date.getTime() - offset).toISOString().slice(0, 16);
+}
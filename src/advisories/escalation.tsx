@@ -0,0 +1,72 @@
+import { EscalationRule } from "@app/bindings/EscalationRule";
+import { invoke } from "@tauri-apps/api/core";
+import CloseIcon from "mdi-preact/CloseIcon";
+import DeleteIcon from "mdi-preact/DeleteIcon";
+import PlusIcon from "mdi-preact/PlusIcon";
+import { useEffect, useState } from "preact/hooks";
+
+const inputClass = "w-full p-2 border border-gray-300 dark:border-gray-700 rounded";
+const levels = ["None", "Low", "Medium", "High", "Maximum"];
+
+function newRule(): EscalationRule {
+  return {
+    id: `vrcmrd_esc_${Date.now().toString(36)}`,
+    name: "Several Medium advisories",
+    min_score: 6,
+    level: 3 as any,
+    message_template: "{{:username:}} matches {{:advisory_count:}} advisories (risk score {{:risk_score:}})",
+    active: true,
+    send_notification: true,
+    send_tts: false,
+  };
+}
+
+/** Editor for the escalation rules, which raise a user's level when their risk score passes a threshold. */
+export default function EscalationRulesEditor({ setOverlay }: {
+  setOverlay?: (overlay: preact.VNode|null) => void,
+}) {
+  const [rules, setRules] = useState<EscalationRule[]>([]);
+  const [error, setError] = useState<string | null>(null);
+  useEffect(() => {
+    invoke<EscalationRule[]>("get_escalation_rules").then(setRules);
+  }, []);
+  const update = (index: number, rule: Partial<EscalationRule>) => setRules(rules.map((r, i) => i === index ? { ...r, ...rule } : r));
+  return <div class="select-none h-full w-full p-2"><div class="max-w-3xl ml-auto flex flex-col bg-white dark:bg-gray-800 text-gray-900 dark:text-gray-100 h-full w-full px-6 py-2 rounded shadow-lg">
+    <div class="w-full flex flex-row mb-4 gap-2 items-center">
+      <button class="inline-block bg-transparent hover:bg-black/20 hover:dark:bg-white/20 text-white transition rounded-full p-2 m-2" onClick={() => setOverlay?.(null)} aria-label="Close"><CloseIcon /></button>
+      <span class="text-2xl font-bold">Escalation Rules</span>
+    </div>
+    <div class="overflow-y-auto h-[calc(100vh-7rem)] flex flex-col gap-4">
+      <p class="text-sm text-gray-600 dark:text-gray-400">Each advisory adds its weight to a user's risk score. When the score reaches a rule's threshold, the user is treated as the rule's level and a notice is sent. By default, Low weighs 1, Medium 2, High 4 and Maximum 8.</p>
+      {rules.map((rule, index) => <div class="flex flex-col gap-2 p-2 border border-gray-300 dark:border-gray-700 rounded">
+        <div class="flex flex-row gap-2 items-center">
+          <input type="checkbox" checked={rule.active} onChange={(e) => update(index, { active: (e.target as HTMLInputElement).checked })} aria-label="Active" />
+          <input type="text" class={inputClass} value={rule.name} onInput={(e) => update(index, { name: (e.target as HTMLInputElement).value })} />
+          <button class="bg-transparent hover:bg-black/20 hover:dark:bg-white/20 hover:text-red-400 transition rounded-full p-1" onClick={() => setRules(rules.filter((_, i) => i !== index))} aria-label="Remove rule"><DeleteIcon /></button>
+        </div>
+        <div class="flex flex-row gap-2 items-center">
+          <label class="whitespace-nowrap">Score at least</label>
+          <input type="number" min={1} class={inputClass} value={rule.min_score} onInput={(e) => update(index, { min_score: parseInt((e.target as HTMLInputElement).value) || 0 })} />
+          <label class="whitespace-nowrap">counts as</label>
+          <select class={inputClass} value={rule.level as any} onChange={(e) => update(index, { level: parseInt((e.target as HTMLSelectElement).value) as any })}>
+            {levels.map((label, level) => <option value={level}>{label}</option>)}
+          </select>
+        </div>
+        <textarea class={inputClass} rows={2} value={rule.message_template} onInput={(e) => update(index, { message_template: (e.target as HTMLTextAreaElement).value })} />
+        <div class="flex flex-row gap-4">
+          <label class="flex items-center gap-2"><input type="checkbox" checked={rule.send_notification} onChange={(e) => update(index, { send_notification: (e.target as HTMLInputElement).checked })} />Notification</label>
+          <label class="flex items-center gap-2"><input type="checkbox" checked={rule.send_tts} onChange={(e) => update(index, { send_tts: (e.target as HTMLInputElement).checked })} />TTS</label>
+        </div>
+      </div>)}
+      <button class="self-start px-2 py-1 bg-gray-200 dark:bg-gray-700 text-gray-800 dark:text-gray-200 rounded-full text-sm hover:bg-gray-300 dark:hover:bg-gray-600" onClick={() => setRules([...rules, newRule()])}>
+        <PlusIcon class="inline align-middle w-4 h-4 mr-1 mb-1" />Add rule
+      </button>
+      {error && <p class="text-sm text-red-500">{error}</p>}
+      <div class="mt-6 flex flex-row gap-4 justify-end">
+        <button class="px-4 py-2 bg-blue-600 hover:bg-blue-700 active:bg-blue-800 text-white rounded" onClick={() => {
+          invoke("set_escalation_rules", { rules }).then(() => setOverlay?.(null)).catch(e => setError(String(e)));
+        }}>Save Changes</button>
+      </div>
+    </div>
+  </div></div>;
+}
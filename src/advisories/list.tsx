@@ -1,4 +1,5 @@
 import { Advisory } from "@app/bindings/Advisory";
+import { AdvisoryStats } from "@app/bindings/AdvisoryStats";
 import { invoke } from "@tauri-apps/api/core";
 import { useEffect, useMemo, useState } from "preact/hooks";
 import AdvisoryEditor from "./editor";
@@ -13,6 +14,7 @@ export default function AdvisoryList({ setOverlay, setDialog }: {
 }) {
   const [loading, setLoading] = useState(true);
   const [advisories, setAdvisories] = useState<Advisory[]>([]);
+  const [stats, setStats] = useState<Record<string, AdvisoryStats>>({});
   const [epoch, setEpoch] = useState(0);
   const [filter, setFilter] = useState("");
   useEffect(() => {
@@ -20,6 +22,9 @@ export default function AdvisoryList({ setOverlay, setDialog }: {
       setAdvisories(advisories);
       setLoading(false);
     });
+    invoke<Record<string, AdvisoryStats>>("get_advisory_stats").then((stats) => {
+      setStats(stats);
+    });
     const updateListener = listen("vrcmrd:advisories_updated", (_) => {
       setEpoch((e) => e + 1);
     });
@@ -87,6 +92,12 @@ export default function AdvisoryList({ setOverlay, setDialog }: {
       }}>
         <h2 class="text-xl font-bold mb-2">{advisory.name}</h2>
         <p class="mb-2 text-sm text-gray-600 dark:text-gray-400">Message: {advisory.message_template}</p>
+        {stats[advisory.id] 
+        ? <p class="text-xs italic text-gray-500 dark:text-gray-400">
+            Matched {stats[advisory.id].totalMatches} time{stats[advisory.id].totalMatches !== 1 && "s"} ({stats[advisory.id].matchedUserCount} user{stats[advisory.id].matchedUserCount !== 1 && "s"})
+            {stats[advisory.id].lastFiredAt && <>, last on {new Date(stats[advisory.id].lastFiredAt!).toLocaleString()}</>}
+          </p>
+        : <p class="text-xs italic text-gray-500 dark:text-gray-400">Never matched</p>}
       </div>
     ))}
   </div>;
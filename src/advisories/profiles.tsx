@@ -0,0 +1,89 @@
+import { AdvisoryProfile } from "@app/bindings/AdvisoryProfile";
+import { ProfileRule } from "@app/bindings/ProfileRule";
+import { invoke } from "@tauri-apps/api/core";
+import CloseIcon from "mdi-preact/CloseIcon";
+import DeleteIcon from "mdi-preact/DeleteIcon";
+import PlusIcon from "mdi-preact/PlusIcon";
+import { useEffect, useState } from "preact/hooks";
+
+const inputClass = "w-full p-2 border border-gray-300 dark:border-gray-700 rounded";
+
+function newProfile(): AdvisoryProfile {
+  return {
+    id: `vrcmrd_profile_${Date.now().toString(36)}`,
+    name: "My community",
+    advisory_ids: [],
+    tags: [],
+    rules: [newRule()],
+    active: true,
+  };
+}
+
+function newRule(): ProfileRule {
+  return { owner: "", world: null, instance_type: null, region: null };
+}
+
+/** Splits a comma- or newline-separated list, dropping empty entries. */
+function splitList(value: string): string[] {
+  return value.split(/[,\n]/).map(v => v.trim()).filter(v => v.length > 0);
+}
+
+/** Editor for advisory profiles, which limit advisories to the instances they're meant for. */
+export default function AdvisoryProfilesEditor({ setOverlay }: {
+  setOverlay?: (overlay: preact.VNode|null) => void,
+}) {
+  const [profiles, setProfiles] = useState<AdvisoryProfile[]>([]);
+  const [activeProfile, setActiveProfile] = useState<AdvisoryProfile | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  useEffect(() => {
+    invoke<AdvisoryProfile[]>("get_advisory_profiles").then(setProfiles);
+    invoke<AdvisoryProfile | null>("get_active_advisory_profile").then(setActiveProfile);
+  }, []);
+  const update = (index: number, profile: Partial<AdvisoryProfile>) => setProfiles(profiles.map((p, i) => i === index ? { ...p, ...profile } : p));
+  const updateRule = (index: number, ruleIndex: number, rule: Partial<ProfileRule>) =>
+    update(index, { rules: profiles[index].rules.map((r, i) => i === ruleIndex ? { ...r, ...rule } : r) });
+  const ruleInput = (index: number, ruleIndex: number, field: keyof ProfileRule, placeholder: string) =>
+    <input type="text" class={inputClass} placeholder={placeholder} value={profiles[index].rules[ruleIndex][field] ?? ""}
+      onInput={(e) => updateRule(index, ruleIndex, { [field]: (e.target as HTMLInputElement).value || null })} />;
+  return <div class="select-none h-full w-full p-2"><div class="max-w-3xl ml-auto flex flex-col bg-white dark:bg-gray-800 text-gray-900 dark:text-gray-100 h-full w-full px-6 py-2 rounded shadow-lg">
+    <div class="w-full flex flex-row mb-4 gap-2 items-center">
+      <button class="inline-block bg-transparent hover:bg-black/20 hover:dark:bg-white/20 text-white transition rounded-full p-2 m-2" onClick={() => setOverlay?.(null)} aria-label="Close"><CloseIcon /></button>
+      <span class="text-2xl font-bold">Advisory Profiles</span>
+    </div>
+    <div class="overflow-y-auto h-[calc(100vh-7rem)] flex flex-col gap-4">
+      <p class="text-sm text-gray-600 dark:text-gray-400">A profile limits advisories to the instances it matches, i.e. one profile per group you moderate for. The first profile with a matching rule is used. When none match, every active advisory applies.</p>
+      <p class="text-sm">Current instance: <span class="font-bold">{activeProfile ? activeProfile.name : "no profile (all advisories)"}</span></p>
+      {profiles.map((profile, index) => <div class="flex flex-col gap-2 p-2 border border-gray-300 dark:border-gray-700 rounded">
+        <div class="flex flex-row gap-2 items-center">
+          <input type="checkbox" checked={profile.active} onChange={(e) => update(index, { active: (e.target as HTMLInputElement).checked })} aria-label="Active" />
+          <input type="text" class={inputClass} value={profile.name} onInput={(e) => update(index, { name: (e.target as HTMLInputElement).value })} />
+          <button class="bg-transparent hover:bg-black/20 hover:dark:bg-white/20 hover:text-red-400 transition rounded-full p-1" onClick={() => setProfiles(profiles.filter((_, i) => i !== index))} aria-label="Remove profile"><DeleteIcon /></button>
+        </div>
+        <label class="text-xs italic text-gray-400">Advisories with these tags (comma-separated)</label>
+        <input type="text" class={inputClass} value={profile.tags.join(", ")} onChange={(e) => update(index, { tags: splitList((e.target as HTMLInputElement).value) })} />
+        <label class="text-xs italic text-gray-400">Advisory IDs (one per line)</label>
+        <textarea class={`${inputClass} font-mono text-sm`} rows={3} value={profile.advisory_ids.join("\n")} onChange={(e) => update(index, { advisory_ids: splitList((e.target as HTMLTextAreaElement).value) })} />
+        <label class="text-xs italic text-gray-400">Applies in instances matching any of these rules. Leave a field empty to match anything.</label>
+        {profile.rules.map((_, ruleIndex) => <div class="flex flex-row gap-2 items-center">
+          {ruleInput(index, ruleIndex, "owner", "Owner (grp_*** or usr_***)")}
+          {ruleInput(index, ruleIndex, "world", "World (wrld_***)")}
+          {ruleInput(index, ruleIndex, "instance_type", "Type, i.e. group public")}
+          {ruleInput(index, ruleIndex, "region", "Region, i.e. us")}
+          <button class="bg-transparent hover:bg-black/20 hover:dark:bg-white/20 hover:text-red-400 transition rounded-full p-1" onClick={() => update(index, { rules: profile.rules.filter((_, i) => i !== ruleIndex) })} aria-label="Remove rule"><DeleteIcon /></button>
+        </div>)}
+        <button class="self-start px-2 py-1 bg-gray-200 dark:bg-gray-700 text-gray-800 dark:text-gray-200 rounded-full text-sm hover:bg-gray-300 dark:hover:bg-gray-600" onClick={() => update(index, { rules: [...profile.rules, newRule()] })}>
+          <PlusIcon class="inline align-middle w-4 h-4 mr-1 mb-1" />Add rule
+        </button>
+      </div>)}
+      <button class="self-start px-2 py-1 bg-gray-200 dark:bg-gray-700 text-gray-800 dark:text-gray-200 rounded-full text-sm hover:bg-gray-300 dark:hover:bg-gray-600" onClick={() => setProfiles([...profiles, newProfile()])}>
+        <PlusIcon class="inline align-middle w-4 h-4 mr-1 mb-1" />Add profile
+      </button>
+      {error && <p class="text-sm text-red-500">{error}</p>}
+      <div class="mt-6 flex flex-row gap-4 justify-end">
+        <button class="px-4 py-2 bg-blue-600 hover:bg-blue-700 active:bg-blue-800 text-white rounded" onClick={() => {
+          invoke("set_advisory_profiles", { profiles }).then(() => setOverlay?.(null)).catch(e => setError(String(e)));
+        }}>Save Changes</button>
+      </div>
+    </div>
+  </div></div>;
+}
@@ -0,0 +1,40 @@
+import { invoke } from "@tauri-apps/api/core";
+import CloseIcon from "mdi-preact/CloseIcon";
+import { useEffect, useState } from "preact/hooks";
+
+/** Editor for the moderator-trusted user list, used by the "Trusted by staff" condition. */
+export default function StaffTrustedUsersEditor({ setOverlay }: {
+  setOverlay?: (overlay: preact.VNode|null) => void,
+}) {
+  const [userIds, setUserIds] = useState("");
+  const [status, setStatus] = useState<string | null>(null);
+  const load = () => invoke<string[]>("get_staff_trusted_users").then(ids => setUserIds(ids.join("\n")));
+  useEffect(() => {
+    load();
+  }, []);
+  return <div class="select-none h-full w-full p-2"><div class="max-w-3xl ml-auto flex flex-col bg-white dark:bg-gray-800 text-gray-900 dark:text-gray-100 h-full w-full px-6 py-2 rounded shadow-lg">
+    <div class="w-full flex flex-row mb-4 gap-2 items-center">
+      <button class="inline-block bg-transparent hover:bg-black/20 hover:dark:bg-white/20 text-white transition rounded-full p-2 m-2" onClick={() => setOverlay?.(null)} aria-label="Close"><CloseIcon /></button>
+      <span class="text-2xl font-bold">Trusted by Staff</span>
+    </div>
+    <div class="overflow-y-auto h-[calc(100vh-7rem)] flex flex-col gap-4">
+      <p class="text-sm text-gray-600 dark:text-gray-400">User IDs your moderators already know, i.e. their friends. Use the "Trusted by staff" condition to treat everyone else differently. One user ID per line.</p>
+      <textarea class="w-full p-2 border border-gray-300 dark:border-gray-700 rounded font-mono text-sm" rows={16} value={userIds} onInput={(e) => setUserIds((e.target as HTMLTextAreaElement).value)} />
+      {status && <p class="text-sm text-gray-600 dark:text-gray-400">{status}</p>}
+      <div class="mt-6 flex flex-row gap-4 justify-end">
+        <button class="px-4 py-2 hover:bg-gray-200 dark:hover:bg-gray-700 rounded" onClick={() => {
+          invoke<number | null>("import_staff_trusted_users").then((added) => {
+            if (added !== null) {
+              setStatus(`Imported ${added} new user${added !== 1 ? "s" : ""}.`);
+              load();
+            }
+          }).catch(e => setStatus(String(e)));
+        }}>Import from file...</button>
+        <button class="px-4 py-2 bg-blue-600 hover:bg-blue-700 active:bg-blue-800 text-white rounded" onClick={() => {
+          const ids = userIds.split("\n").map(id => id.trim()).filter(id => id.length > 0);
+          invoke("set_staff_trusted_users", { userIds: ids }).then(() => setOverlay?.(null)).catch(e => setStatus(String(e)));
+        }}>Save Changes</button>
+      </div>
+    </div>
+  </div></div>;
+}
@@ -4,8 +4,46 @@ import AccountIcon from 'mdi-preact/AccountIcon';
 import HexagonMultipleIcon from 'mdi-preact/HexagonMultipleIcon';
 //import FlagIcon from 'mdi-preact/FlagIcon';
 import { invoke } from '@tauri-apps/api/core';
+import { NoticeState } from '@app/bindings/NoticeState';
+import { useState } from 'preact/hooks';
 
-export default function Notice({ notice }: { notice: NoticeType }) {
+function describeState(state: NoticeState) {
+  const time = (at: string) => new Date(at).toLocaleTimeString();
+  switch (state.type) {
+    case "New": return null;
+    case "Acknowledged": return <>Acknowledged{state.data.by && <> by {state.data.by}</>} at {time(state.data.at)}</>;
+    case "Claimed": return <>Claimed by {state.data.by} at {time(state.data.at)}</>;
+    case "Resolved": return <>Resolved{state.data.by && <> by {state.data.by}</>} at {time(state.data.at)}{state.data.note && <>: {state.data.note}</>}</>;
+  }
+}
+
+/** Acknowledge, claim and resolve buttons. Only for notices in the current instance. */
+function NoticeActions({ notice }: { notice: NoticeType }) {
+  const [resolving, setResolving] = useState(false);
+  const [note, setNote] = useState("");
+  const [error, setError] = useState<string | null>(null);
+  const buttonClasses = "px-2 py-1 rounded bg-black/10 dark:bg-white/10 hover:bg-black/20 hover:dark:bg-white/20 text-xs";
+  const run = (command: string, args: Record<string, unknown> = {}) =>
+    invoke(command, { id: notice.id, ...args }).then(() => setError(null)).catch((e) => setError(String(e)));
+  if (resolving) {
+    return <div class="flex flex-row flex-wrap gap-2 items-center mb-2">
+      <input type="text" class="flex-1 p-1 rounded bg-white/40 dark:bg-black/20 border border-current/20 text-sm" placeholder="What was done?" value={note}
+        onInput={(e) => setNote((e.target as HTMLInputElement).value)} />
+      <button class={buttonClasses} onClick={() => run("resolve_notice", { note }).then(() => setResolving(false))}>Resolve</button>
+      <button class={buttonClasses} onClick={() => setResolving(false)}>Cancel</button>
+      {error && <span class="text-xs text-red-500">{error}</span>}
+    </div>;
+  }
+  return <div class="flex flex-row flex-wrap gap-2 items-center mb-2">
+    {notice.state.type === "New" && <button class={buttonClasses} onClick={() => run("acknowledge_notice")}>Acknowledge</button>}
+    {(notice.state.type === "New" || notice.state.type === "Acknowledged") && <button class={buttonClasses} onClick={() => run("claim_notice")}>Claim</button>}
+    <button class={buttonClasses} onClick={() => setResolving(true)}>{notice.state.type === "Resolved" ? "Edit note" : "Resolve"}</button>
+    {notice.state.type !== "New" && <button class={buttonClasses} onClick={() => run("reopen_notice")}>Reopen</button>}
+    {error && <span class="text-xs text-red-500">{error}</span>}
+  </div>;
+}
+
+export default function Notice({ notice, actions }: { notice: NoticeType, actions?: boolean }) {
   const chipClasses = "flex flex-row items-center cursor-pointer gap-1 mr-2 px-2 py-1 bg-gray-200/40 dark:bg-gray-700/40 hover:bg-gray-300/60 dark:hover:bg-gray-600/60 border rounded-full text-xs";
   // function trim(str: string, maxLength: number) {
   //   if (str.length <= maxLength) return str;
@@ -30,6 +68,11 @@ export default function Notice({ notice }: { notice: NoticeType }) {
       <div class="px-4 py-2">
         {notice.title && <h2 class="text-lg font-semibold mb-2">{notice.title}</h2>}
         <p class="mb-2">{notice.message}</p>
+        {notice.repeatCount > 0 && <p class="mb-2 text-xs italic">
+          Repeated {notice.repeatCount} more time{notice.repeatCount !== 1 && "s"}{notice.lastRepeatedAt && <>, last at {new Date(notice.lastRepeatedAt).toLocaleTimeString()}</>}
+        </p>}
+        {notice.state.type !== "New" && <p class="mb-2 text-xs font-semibold">{describeState(notice.state)}</p>}
+        {actions && notice.id > 0 && <NoticeActions notice={notice} />}
       </div>
       <div class={`rounded-b-lg p-4 flex flex-row gap-4 items-center text-sm ${notice.level as any === 0
           ? 'bg-gray-100 dark:bg-gray-800 text-gray-600 dark:text-gray-300'
@@ -1,5 +1,5 @@
 /// <reference types="vite-plugin-svgr/client" />
-import { getHighestAdvisoryLevel, TrustRank, User } from '../data/users';
+import { getEffectiveAdvisoryLevel, TrustRank, User } from '../data/users';
 import AndroidIcon from "mdi-preact/AndroidIcon";
 import AppleIcon from "mdi-preact/AppleIcon";
 import MonitorIcon from "mdi-preact/MonitorIcon";
@@ -10,6 +10,8 @@ import InfoFilledIcon from "mdi-preact/InformationIcon";
 import ErrorIcon from "mdi-preact/AlertIcon";
 import StopIcon from "mdi-preact/AlertOctagonIcon";
 import AccountRemoveIcon from "mdi-preact/AccountRemoveIcon";
+import AccountAlertIcon from "mdi-preact/AccountAlertIcon";
+import ShieldAccountIcon from "mdi-preact/ShieldAccountIcon";
 import { useEffect, useState } from 'preact/hooks';
 import { listen } from '@tauri-apps/api/event';
 import { menu } from '@tauri-apps/api';
@@ -31,9 +33,13 @@ export default function UserTable() {
       .sort((a, b) => {
         // Sort by advisory level, but only for users currently in-world (leaveTime === null)
         // TODO: make this a preference
-        const aLevel = a.leaveTime === null ? getHighestAdvisoryLevel(a.advisories) ?? 0 : 0;
-        const bLevel = b.leaveTime === null ? getHighestAdvisoryLevel(b.advisories) ?? 0 : 0;
-        return bLevel - aLevel;
+        const aLevel = a.leaveTime === null ? getEffectiveAdvisoryLevel(a) : 0;
+        const bLevel = b.leaveTime === null ? getEffectiveAdvisoryLevel(b) : 0;
+        if (aLevel !== bLevel) return bLevel - aLevel;
+        // Then by risk score, so users matching several advisories rank above users matching one
+        const aScore = a.leaveTime === null ? a.riskScore ?? 0 : 0;
+        const bScore = b.leaveTime === null ? b.riskScore ?? 0 : 0;
+        return bScore - aScore;
       })
       .sort((a, b) => {
         // Users currently in-world first (leaveTime === null)
@@ -202,14 +208,14 @@ export default function UserTable() {
                 <td class="px-2 py-1 align-middle overflow-hidden flex-grow">
                   <div class="flex items-center justify-end gap-2">
                   {u.advisories.length > 0 && (
-                    <div data-tooltip-id="tooltip" data-tooltip-content={`${u.advisories.length} advisor${u.advisories.length !== 1 ? 'ies' : 'y'}`}>
+                    <div data-tooltip-id="tooltip" data-tooltip-content={`${u.advisories.length} advisor${u.advisories.length !== 1 ? 'ies' : 'y'}${u.riskScore ? `, risk score ${u.riskScore}` : ''}${u.escalatedBy ? ' (escalated)' : ''}`}>
                       {{
                         0: <InfoOutlineIcon class="w-5 h-5 text-black dark:text-white" />,
                         1: <InfoFilledIcon class="w-5 h-5 text-blue-400" />,
                         2: <AlertIcon class="w-5 h-5 text-yellow-400" />,
                         3: <ErrorIcon class="w-5 h-5 text-orange-400" />,
                         4: <StopIcon class="w-5 h-5 text-red-400" />,
-                      }[getHighestAdvisoryLevel(u.advisories) ?? 0]}
+                      }[getEffectiveAdvisoryLevel(u)]}
                       {/* Show advisory message if only one at highest level */}
                     </div>
                   )}
@@ -220,6 +226,18 @@ export default function UserTable() {
                     </div>
                   )}
 
+                  {u.isGroupStaff && (
+                    <div data-tooltip-id="tooltip" data-tooltip-content="Staff in the group hosting this instance">
+                      <ShieldAccountIcon class="w-5 h-5 text-green-400" />
+                    </div>
+                  )}
+
+                  {(u.flags?.probableTroll || u.flags?.troll) && (
+                    <div data-tooltip-id="tooltip" data-tooltip-content={u.flags?.troll ? "Marked as a troll by VRChat" : "Probable troll (flagged by VRChat)"}>
+                      <AccountAlertIcon class="w-5 h-5 text-red-400" />
+                    </div>
+                  )}
+
                   {/* keeping this for preferences where age verification is shown separately,
                       such as when trust rank icons are hidden but age verification is shown,
                       or when they're specifically set to show separately.
@@ -1,3 +1,4 @@
+import { ConditionTrace } from "@app/bindings/ConditionTrace";
 import { LimitedUserInstance } from "vrchat";
 
 export type Platform = 'pc' | 'android' | 'ios';
@@ -9,15 +10,33 @@ export type User = {
   avatarName: string;
   pronouns: string | null;
   status: string | null;
+  bio?: string | null;
+  bioLinks?: string[];
   perfRank?: PerformanceRank;
+  /** The avatar's release status from avatar search, i.e. "public" or "private". */
+  avatarReleaseStatus?: string | null;
+  /** Avatars the user may be wearing, with confidence out of 100. */
+  avatarCandidates?: { avatarId: string, confidence: number }[];
   accountCreated: number | null; // e.g. "3y"
   joinTime: number; // e.g. "13:12"
   leaveTime: number | null; // e.g. "13:24"
   advisories: ActiveAdvisory[];
   ageVerified: boolean;
+  /** Whether the logged-in account is friends with the user. */
+  isFriend?: boolean;
+  /** Whether the user holds a management role in the group hosting the instance. */
+  isGroupStaff?: boolean;
   platform: Platform | null;
   recentlyKicked: boolean;
   trustRank?: TrustRank;
+  /** Raw VRChat tags, i.e. system_trust_basic. */
+  tags?: string[];
+  flags?: UserFlags;
+  /** The sum of the weights of the user's matching advisories. */
+  riskScore?: number;
+  /** The highest advisory level, raised by escalation rules. */
+  effectiveLevel?: 0 | 1 | 2 | 3 | 4 | null;
+  escalatedBy?: string | null;
 };
 
 export type GetUserInfoResponse = {
@@ -38,11 +57,28 @@ export function getHighestAdvisoryLevel(advisories: ActiveAdvisory[]): number {
   return highestLevel;
 }
 
+/** The user's advisory level for sorting and icons, including escalation. */
+export function getEffectiveAdvisoryLevel(user: User): number {
+  return Math.max(user.effectiveLevel ?? 0, getHighestAdvisoryLevel(user.advisories));
+}
+
 export type ActiveAdvisory = {
   id: string;
   message: string;
   level: 0 | 1 | 2 | 3 | 4;
   relevantGroupId?: string;
+  /** How the advisory's condition was evaluated when it last matched. */
+  trace?: ConditionTrace | null;
+};
+
+/** Explicit flags derived from the user's raw tags. Unlike the trust rank, these aren't collapsed into one value. */
+export type UserFlags = {
+  probableTroll: boolean;
+  troll: boolean;
+  admin: boolean;
+  supporter: boolean;
+  earlyAdopter: boolean;
+  languages: string[];
 };
 
 export type TrustRank = 'Nuisance' | 'Visitor' | 'NewUser' | 'User' | 'KnownUser' | 'TrustedUser' | 'Admin';
@@ -8,6 +8,9 @@ import AdvisoryList from "../advisories/list";
 import { invoke } from "@tauri-apps/api/core";
 import defaultAdvisory from "../advisories/default";
 import AdvisoryEditor from "../advisories/editor";
+import EscalationRulesEditor from "../advisories/escalation";
+import StaffTrustedUsersEditor from "../advisories/trusted_users";
+import AdvisoryProfilesEditor from "../advisories/profiles";
 import { Advisory } from "@app/bindings/Advisory";
 import { emit, listen } from "@tauri-apps/api/event";
 
@@ -40,7 +43,15 @@ function ManageAdvisoriesWindow() {
   return <>
     <div class="h-screen w-full overflow-y-auto select-none flex bg-gray-100 text-gray-600 dark:bg-gray-900 dark:text-gray-300">
       <div class="p-6">
-        <h1 class="text-2xl font-bold mb-4">Manage Advisories</h1>
+        <div class="flex flex-row items-center gap-4 mb-4">
+          <h1 class="text-2xl font-bold">Manage Advisories</h1>
+          <button class="px-3 py-1 bg-gray-200 dark:bg-gray-700 text-gray-800 dark:text-gray-200 rounded hover:bg-gray-300 dark:hover:bg-gray-600"
+          onClick={() => setOverlay(<EscalationRulesEditor setOverlay={setOverlay} />)}>Escalation Rules</button>
+          <button class="px-3 py-1 bg-gray-200 dark:bg-gray-700 text-gray-800 dark:text-gray-200 rounded hover:bg-gray-300 dark:hover:bg-gray-600"
+          onClick={() => setOverlay(<StaffTrustedUsersEditor setOverlay={setOverlay} />)}>Trusted by Staff</button>
+          <button class="px-3 py-1 bg-gray-200 dark:bg-gray-700 text-gray-800 dark:text-gray-200 rounded hover:bg-gray-300 dark:hover:bg-gray-600"
+          onClick={() => setOverlay(<AdvisoryProfilesEditor setOverlay={setOverlay} />)}>Profiles</button>
+        </div>
         <AdvisoryList setOverlay={setOverlay} setDialog={setDialog} />
         <button class="fixed bottom-4 right-4 mt-4 px-3 py-3 bg-blue-600 hover:bg-blue-700 active:bg-blue-800 text-white rounded-full shadow-lg"
         aria-label="Add Advisory" data-tooltip-id="tooltip" data-tooltip-content="Add Advisory" 
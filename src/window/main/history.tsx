@@ -0,0 +1,73 @@
+import { NoticePage } from '@app/bindings/NoticePage';
+import { NoticeQuery } from '@app/bindings/NoticeQuery';
+import Notice from '../../components/Notice.tsx';
+import Dropdown from '../../components/Dropdown';
+import { useState, useEffect } from 'preact/hooks';
+import { invoke } from '@tauri-apps/api/core';
+
+const PAGE_SIZE = 50;
+const inputClass = "p-1 border border-gray-300 dark:border-gray-700 rounded bg-transparent text-sm";
+const levelNames = ["Any level", "Low and above", "Medium and above", "High and above", "Maximum only"];
+
+/** Converts a date input's value to a Unix timestamp, in local time. */
+function toTimestamp(value: string, endOfDay: boolean): number | null {
+  if (!value) return null;
+  const date = new Date(`${value}T00:00:00`);
+  if (endOfDay) date.setDate(date.getDate() + 1);
+  return Math.floor(date.getTime() / 1000);
+}
+
+/** Notices from every instance, kept across instance changes and restarts. */
+export default function NoticeHistory() {
+  const [minLevel, setMinLevel] = useState(0);
+  const [userId, setUserId] = useState("");
+  const [advisoryId, setAdvisoryId] = useState("");
+  const [instanceId, setInstanceId] = useState("");
+  const [from, setFrom] = useState("");
+  const [to, setTo] = useState("");
+  const [offset, setOffset] = useState(0);
+  const [page, setPage] = useState<NoticePage | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  useEffect(() => {
+    const query: NoticeQuery = {
+      minLevel: minLevel > 0 ? minLevel as any : null,
+      userId: userId.trim() || null,
+      advisoryId: advisoryId.trim() || null,
+      instanceId: instanceId.trim() || null,
+      from: toTimestamp(from, false),
+      to: toTimestamp(to, true),
+      offset,
+      limit: PAGE_SIZE,
+    };
+    invoke<NoticePage>('query_notice_history', { query })
+      .then((page) => { setPage(page); setError(null); })
+      .catch((e) => setError(String(e)));
+  }, [minLevel, userId, advisoryId, instanceId, from, to, offset]);
+  // Go back to the first page whenever a filter changes
+  const filter = <T,>(set: (value: T) => void) => (value: T) => { set(value); setOffset(0); };
+  return (<div class="flex-1 min-h-0 min-w-0 w-full overflow-hidden bg-gray-100 dark:bg-gray-900 overflow-y-auto overflow-x-hidden">
+    <div class="flex flex-row flex-wrap gap-2 items-center p-2">
+      <Dropdown items={levelNames.map((label, level) => ({ active: minLevel === level, set: () => filter(setMinLevel)(level), label: <>{label}</> }))} />
+      <input type="text" class={inputClass} placeholder="User ID" value={userId} onChange={(e) => filter(setUserId)((e.target as HTMLInputElement).value)} />
+      <input type="text" class={inputClass} placeholder="Advisory ID" value={advisoryId} onChange={(e) => filter(setAdvisoryId)((e.target as HTMLInputElement).value)} />
+      <input type="text" class={inputClass} placeholder="Instance ID" value={instanceId} onChange={(e) => filter(setInstanceId)((e.target as HTMLInputElement).value)} />
+      <label class="text-xs text-gray-500">From <input type="date" class={inputClass} value={from} onChange={(e) => filter(setFrom)((e.target as HTMLInputElement).value)} /></label>
+      <label class="text-xs text-gray-500">To <input type="date" class={inputClass} value={to} onChange={(e) => filter(setTo)((e.target as HTMLInputElement).value)} /></label>
+    </div>
+    {error && <p class="px-4 text-sm text-red-500">{error}</p>}
+    <div class="flex flex-col space-y-4 w-full min-w-0">
+      {page?.notices.map((stored) => (
+        <div key={`${stored.recordedAt}-${stored.notice.createdAt}`}>
+          <p class="px-4 text-xs text-gray-500">{new Date(stored.recordedAt * 1000).toLocaleString()}{stored.instanceId && <> in <span class="font-mono">{stored.instanceId}</span></>}</p>
+          <Notice notice={stored.notice} />
+        </div>
+      ))}
+      {page && page.total === 0 && <p class="px-4 text-sm italic text-gray-500">No notices match.</p>}
+    </div>
+    {page && page.total > PAGE_SIZE && <div class="flex flex-row gap-2 items-center justify-center p-2 text-sm">
+      <button class="px-2 py-1 rounded bg-gray-200 dark:bg-gray-700 disabled:opacity-50" disabled={offset === 0} onClick={() => setOffset(Math.max(0, offset - PAGE_SIZE))}>Newer</button>
+      <span>{offset + 1}–{Math.min(offset + PAGE_SIZE, page.total)} of {page.total}</span>
+      <button class="px-2 py-1 rounded bg-gray-200 dark:bg-gray-700 disabled:opacity-50" disabled={offset + PAGE_SIZE >= page.total} onClick={() => setOffset(offset + PAGE_SIZE)}>Older</button>
+    </div>}
+  </div>);
+}
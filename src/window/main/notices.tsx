@@ -1,4 +1,5 @@
 import { Notice as NoticeType } from '@app/bindings/Notice';
+import { NoticeCursorPage } from '@app/bindings/NoticeCursorPage';
 import Notice from '../../components/Notice.tsx';
 import { useState, useEffect } from 'preact/hooks';
 import { invoke } from '@tauri-apps/api/core';
@@ -6,31 +7,45 @@ import { listen } from '@tauri-apps/api/event';
 
 export default function NoticesList() {
   const [notices, setNotices] = useState<NoticeType[]>([]);
+  const [nextCursor, setNextCursor] = useState<number | null>(null);
   useEffect(() => {
-    invoke<NoticeType[]>('get_all_notices').then((fetchedNotices: NoticeType[]) => {
-      setNotices(fetchedNotices);
-    });
+    const loadNewest = () => {
+      invoke<NoticeCursorPage>('get_notices', { before: null }).then((page) => {
+        setNotices(page.notices);
+        setNextCursor(page.nextCursor);
+      });
+    };
+    loadNewest();
     const listener = listen('vrcmrd:notice', (event) => {
       const newNotice = event.payload as NoticeType;
       setNotices((prevNotices) => [newNotice, ...prevNotices]);
     });
-    const reloadListener = () => {
-      invoke<NoticeType[]>('get_all_notices').then((fetchedNotices: NoticeType[]) => {
-        setNotices(fetchedNotices);
-      });
-    };
-    document.addEventListener('vrcmrd:soft-reload', reloadListener);
+    const updateListener = listen('vrcmrd:notice_updated', (event) => {
+      const updatedNotice = event.payload as NoticeType;
+      setNotices((prevNotices) => prevNotices.map((notice) => notice.id === updatedNotice.id ? updatedNotice : notice));
+    });
+    document.addEventListener('vrcmrd:soft-reload', loadNewest);
     return () => {
       listener.then((unlisten) => unlisten());
-      document.removeEventListener('vrcmrd:soft-reload', reloadListener);
+      updateListener.then((unlisten) => unlisten());
+      document.removeEventListener('vrcmrd:soft-reload', loadNewest);
     };
   }, []);
+  const loadOlder = () => {
+    invoke<NoticeCursorPage>('get_notices', { before: nextCursor }).then((page) => {
+      setNotices((prevNotices) => [...prevNotices, ...page.notices]);
+      setNextCursor(page.nextCursor);
+    });
+  };
   return (<div class="flex-1 min-h-0 min-w-0 w-full overflow-hidden bg-gray-100 dark:bg-gray-900 overflow-y-auto overflow-x-hidden">
     <div class="flex flex-col space-y-4 h-full w-full min-h-0 min-w-0">
       {notices.map((notice) => (
-        <Notice key={notice.createdAt} notice={notice} />
+        <Notice key={notice.id} notice={notice} actions />
       ))}
+      {nextCursor !== null && <button class="self-center px-2 py-1 rounded bg-gray-200 dark:bg-gray-700 text-sm" onClick={loadOlder}>Load older notices</button>}
       <Notice notice={{
+        id: 0,
+        revision: 0,
         title: "Welcome to VRCMRD!",
         message: "This is a sample notice to demonstrate the notice system. You can manage advisories and view important information here.",
         level: 4 as any,
@@ -41,8 +56,13 @@ export default function NoticesList() {
         relevantGroupId: null,
         relevantAdvisoryId: null,
         relevantUserId: null,
+        repeatCount: 0,
+        lastRepeatedAt: null,
+        state: { type: "New" },
       }} />
       <Notice notice={{
+        id: 0,
+        revision: 0,
         title: "Welcome to VRCMRD!",
         message: "This is a sample notice to demonstrate the notice system. You can manage advisories and view important information here.",
         level: 3 as any,
@@ -53,8 +73,13 @@ export default function NoticesList() {
         relevantGroupId: null,
         relevantAdvisoryId: null,
         relevantUserId: null,
+        repeatCount: 0,
+        lastRepeatedAt: null,
+        state: { type: "New" },
       }} />
       <Notice notice={{
+        id: 0,
+        revision: 0,
         title: "Welcome to VRCMRD!",
         message: "This is a sample notice to demonstrate the notice system. You can manage advisories and view important information here.",
         level: 2 as any,
@@ -65,8 +90,13 @@ export default function NoticesList() {
         relevantGroupId: null,
         relevantAdvisoryId: null,
         relevantUserId: null,
+        repeatCount: 0,
+        lastRepeatedAt: null,
+        state: { type: "New" },
       }} />
       <Notice notice={{
+        id: 0,
+        revision: 0,
         title: "Welcome to VRCMRD!",
         message: "This is a sample notice to demonstrate the notice system. You can manage advisories and view important information here.",
         level: 1 as any,
@@ -77,8 +107,13 @@ export default function NoticesList() {
         relevantGroupId: null,
         relevantAdvisoryId: null,
         relevantUserId: null,
+        repeatCount: 0,
+        lastRepeatedAt: null,
+        state: { type: "New" },
       }} />
       <Notice notice={{
+        id: 0,
+        revision: 0,
         title: "Welcome to VRCMRD!",
         message: "This is a sample notice to demonstrate the notice system. You can manage advisories and view important information here.",
         level: 0 as any,
@@ -89,6 +124,9 @@ export default function NoticesList() {
         relevantGroupId: null,
         relevantAdvisoryId: null,
         relevantUserId: null,
+        repeatCount: 0,
+        lastRepeatedAt: null,
+        state: { type: "New" },
       }} />
     </div>
   </div>);
@@ -8,6 +8,8 @@ import CheckIcon from "mdi-preact/CheckIcon";
 export default function PreferencesPage({ loading }: { loading: boolean }) {
   const [notificationPreference, setNotificationPreference] = useState("1");
   const [ttsPreference, setTtsPreference] = useState("1");
+  const [changeNotices, setChangeNotices] = useState("1");
+  const [alertBatchWindow, setAlertBatchWindow] = useState("3");
   function set(key: string, value: string) {
     invoke("update_config", { key, value }).catch((e) => {
       console.error(`Failed to set config ${key} to ${value}:`, e);
@@ -25,12 +27,26 @@ export default function PreferencesPage({ loading }: { loading: boolean }) {
         setTtsPreference(value);
       }
     });
+    invoke("get_config", { key: "advisory_change_notices" }).then((value) => {
+      if (typeof value === "string") {
+        setChangeNotices(value);
+      }
+    });
+    invoke("get_config", { key: "alert_batch_window_seconds" }).then((value) => {
+      if (typeof value === "string") {
+        setAlertBatchWindow(value);
+      }
+    });
     let updated = listen("vrcmrd:config_updated", (event) => {
       const { key, value } = event.payload as { key: string, value: string };
       if (key === "notification_preference") {
         setNotificationPreference(value);
       } else if (key === "tts_preference") {
         setTtsPreference(value);
+      } else if (key === "advisory_change_notices") {
+        setChangeNotices(value);
+      } else if (key === "alert_batch_window_seconds") {
+        setAlertBatchWindow(value);
       }
     });
     return () => {
@@ -63,6 +79,23 @@ export default function PreferencesPage({ loading }: { loading: boolean }) {
         // { active: ttsPreference === "3", set: () => set("tts_preference", "3"), label: <>Read out ALL advisories</> }
       ]} />
     </div>
+    <div className="space-y-2">
+      <label className="block text-xs text-gray-600 dark:text-gray-400">When advisories change, for users already in the instance</label>
+      <Dropdown items={[
+        { active: changeNotices === "0", set: () => set("advisory_change_notices", "0"), label: <>Apply silently</> },
+        { active: changeNotices === "1", set: () => set("advisory_change_notices", "1"), label: <>Send a notice for each match</> },
+        { active: changeNotices === "2", set: () => set("advisory_change_notices", "2"), label: <>Send one summary notice</> },
+      ]} />
+    </div>
+    <div className="space-y-2">
+      <label className="block text-xs text-gray-600 dark:text-gray-400">When many users match at once (Maximum-level notices always alert right away)</label>
+      <Dropdown items={[
+        { active: alertBatchWindow === "0", set: () => set("alert_batch_window_seconds", "0"), label: <>Alert for each notice</> },
+        { active: alertBatchWindow === "3", set: () => set("alert_batch_window_seconds", "3"), label: <>Group alerts within 3 seconds into one</> },
+        { active: alertBatchWindow === "10", set: () => set("alert_batch_window_seconds", "10"), label: <>Group alerts within 10 seconds into one</> },
+        { active: alertBatchWindow === "30", set: () => set("alert_batch_window_seconds", "30"), label: <>Group alerts within 30 seconds into one</> },
+      ]} />
+    </div>
     <div className="space-y-2">
       <label className="block text-xs text-gray-600 dark:text-gray-400">Age verification in user list</label>
       <Dropdown items={[
@@ -74,9 +107,59 @@ export default function PreferencesPage({ loading }: { loading: boolean }) {
     </div>
     <CheckboxPreference label="Show platform in user list" configKey="show_platform" />
     <CheckboxPreference label="Look up group name for instances" configKey="instance_lookup_group_name" defaultValue={true} />
+    <NumberPreference label="Remember kicks and bans for (hours)" configKey="moderation_history_hours" defaultValue={24} />
+    <NumberPreference label="Re-check advisories every (seconds, 0 to turn off)" configKey="reevaluation_interval_seconds" defaultValue={60} />
+    <NumberPreference label="Keep notice history for (days, 0 to keep forever)" configKey="notice_retention_days" defaultValue={30} />
+    <TextPreference label="Your name, shown when you acknowledge, claim or resolve notices" configKey="moderator_name" />
+    <CheckboxPreference label="Skip pending TTS and repeat alerts once a notice is acknowledged" configKey="acknowledge_cancels_alerts" />
   </div>
 }
 
+export function NumberPreference({ label, configKey, defaultValue } : { label: preact.VNode | string, configKey: string, defaultValue: number }) {
+  const [value, setValue] = useState(defaultValue);
+  useEffect(() => {
+    invoke("get_config", { key: configKey }).then((value) => {
+      if (typeof value === "string" && !isNaN(parseInt(value))) {
+        setValue(parseInt(value));
+      }
+    });
+  }, [configKey]);
+  return <div className="space-y-2">
+    <label className="block text-xs text-gray-600 dark:text-gray-400">{label}</label>
+    <input type="number" min={1} className="w-32 p-1 rounded bg-white/10 ring-1 ring-white/15 text-sm text-gray-800 dark:text-gray-200" value={value} onChange={(e) => {
+      const parsed = parseInt((e.target as HTMLInputElement).value);
+      if (isNaN(parsed) || parsed < 1) return;
+      setValue(parsed);
+      invoke("update_config", { key: configKey, value: parsed.toString() }).catch((e) => {
+        console.error(`Failed to set config ${configKey} to ${parsed}:`, e);
+        alert(`Could not save preference ${configKey}. Please try again.`);
+      });
+    }} />
+  </div>;
+}
+
+export function TextPreference({ label, configKey } : { label: preact.VNode | string, configKey: string }) {
+  const [value, setValue] = useState("");
+  useEffect(() => {
+    invoke("get_config", { key: configKey }).then((value) => {
+      if (typeof value === "string") {
+        setValue(value);
+      }
+    });
+  }, [configKey]);
+  return <div className="space-y-2">
+    <label className="block text-xs text-gray-600 dark:text-gray-400">{label}</label>
+    <input type="text" className="w-64 p-1 rounded bg-white/10 ring-1 ring-white/15 text-sm text-gray-800 dark:text-gray-200" value={value} onChange={(e) => {
+      const updated = (e.target as HTMLInputElement).value.trim();
+      setValue(updated);
+      invoke("update_config", { key: configKey, value: updated }).catch((e) => {
+        console.error(`Failed to set config ${configKey} to ${updated}:`, e);
+        alert(`Could not save preference ${configKey}. Please try again.`);
+      });
+    }} />
+  </div>;
+}
+
 export function CheckboxPreference({ label, configKey, defaultValue } : { label: preact.VNode | string, configKey: string, defaultValue?: boolean }) {
   const [enabled, setEnabled] = useState(defaultValue ?? false);
   function set(value: boolean) {
@@ -14,12 +14,30 @@ import InfoFilledIcon from "mdi-preact/InformationIcon";
 import ErrorIcon from "mdi-preact/AlertIcon";
 import StopIcon from "mdi-preact/AlertOctagonIcon";
 import { listen } from "@tauri-apps/api/event";
+import { ConditionTrace } from "@app/bindings/ConditionTrace";
+
+/** A tree of how an advisory's condition was evaluated, with the user's values next to each condition. */
+function ConditionTraceView({ trace }: { trace: ConditionTrace }) {
+  const result = trace.skipped ? "skipped" : trace.matched ? "matched" : "did not match";
+  const color = trace.skipped ? "text-gray-400" : trace.matched ? "text-green-600 dark:text-green-400" : "text-red-500 dark:text-red-400";
+  return <li>
+    <span className="font-mono">{trace.condition}</span>
+    {trace.parameter && <span className="font-mono text-gray-500 dark:text-gray-400">{` ${trace.parameter}`}</span>}
+    {" "}<span className={color}>{result}</span>
+    {trace.value !== null && !trace.skipped && <span className="italic text-gray-500 dark:text-gray-400">{` (value: ${trace.value})`}</span>}
+    {trace.keptExisting && <span className="italic text-gray-500 dark:text-gray-400"> (kept, data not available)</span>}
+    {trace.children.length > 0 && <ul className="ml-4 list-none">
+      {trace.children.map((child, idx) => <ConditionTraceView key={idx} trace={child} />)}
+    </ul>}
+  </li>;
+}
 
 export default function UserDetailsWindowContents() {
   const userId = window.location.hash.substring(1);
   const [userInfo, setUserInfo] = useState<GetUserInfoResponse|null>(null);
   const [userGroups, setUserGroups] = useState<vrc.LimitedUserGroups[]|null>(null);
   const [loading, setLoading] = useState<boolean>(true);
+  const [openTrace, setOpenTrace] = useState<string | null>(null);
   useEffect(() => {
     setLoading(true);
     invoke<GetUserInfoResponse>("get_user_info", { userId })
@@ -93,7 +111,11 @@ export default function UserDetailsWindowContents() {
               3: <ErrorIcon className="inline align-middle w-4 h-4 text-orange-400 mr-1 mb-1" />,
               4: <StopIcon className="inline align-middle w-4 h-4 text-red-400 mr-1 mb-1" />,
             }[adv.level]}
-            {adv.message}{adv.relevantGroupId && <>{` (Group: `}<a href={`https://vrchat.com/home/group/${adv.relevantGroupId}`} target="_blank" rel="noopener noreferrer">{adv.relevantGroupId}</a>{`)`}</>}</li>
+            {adv.message}{adv.relevantGroupId && <>{` (Group: `}<a href={`https://vrchat.com/home/group/${adv.relevantGroupId}`} target="_blank" rel="noopener noreferrer">{adv.relevantGroupId}</a>{`)`}</>}
+            {adv.trace && <button className="ml-2 text-xs text-blue-600 dark:text-blue-400 hover:underline" onClick={() => setOpenTrace(openTrace === adv.id ? null : adv.id)}>{openTrace === adv.id ? "Hide details" : "Why?"}</button>}
+            {adv.trace && openTrace === adv.id && <ul className="ml-6 mt-1 text-sm list-none">
+              <ConditionTraceView trace={adv.trace} />
+            </ul>}</li>
           ))}
         </ul>}
     </div>